@@ -0,0 +1,429 @@
+use crate::Ship;
+use crate::weapons::{GunDistributionType, SubBattery};
+
+use serde::Serialize;
+
+// Side {{{1
+/// Side of the ship a mount is on.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Side {
+    Centre,
+    Port,
+    Starboard,
+}
+
+// Bearing {{{1
+/// Number of guns of a battery that bear ahead, astern and on each
+/// broadside.
+///
+#[derive(Serialize, Clone, Copy, Debug, Default, PartialEq)]
+pub struct Bearing {
+    pub ahead: u32,
+    pub astern: u32,
+    pub port: u32,
+    pub starboard: u32,
+}
+
+impl Bearing { // {{{2
+    /// Bearing dead ahead in degrees.
+    pub const AHEAD: f64 = 0.0;
+    /// Bearing on the starboard beam in degrees.
+    pub const STARBOARD: f64 = 90.0;
+    /// Bearing dead astern in degrees.
+    pub const ASTERN: f64 = 180.0;
+    /// Bearing on the port beam in degrees.
+    pub const PORT: f64 = 270.0;
+
+    // of {{{3
+    /// Guns of a battery that bear ahead, astern and on each broadside.
+    ///
+    pub fn of(mounts: &[Mount], battery: usize) -> Self {
+        let guns = |bearing| mounts.iter()
+            .filter(|m| m.battery == battery && m.bears(bearing))
+            .map(|m| m.guns)
+            .sum();
+
+        Self {
+            ahead: guns(Self::AHEAD),
+            astern: guns(Self::ASTERN),
+            port: guns(Self::PORT),
+            starboard: guns(Self::STARBOARD),
+        }
+    }
+}
+
+// Mount {{{1
+/// A single gun mount placed on the ship and the bearings it cannot fire
+/// on.
+///
+/// Bearings are in degrees clockwise from dead ahead. Arcs and sectors are
+/// (from, to) pairs running clockwise, with to ≥ from, so an arc all the
+/// way round is (0, 360).
+///
+#[derive(Clone, Debug)]
+pub struct Mount {
+    /// Battery the mount belongs to.
+    pub battery: usize,
+    /// Number of guns in the mount.
+    pub guns: u32,
+    /// Distance from the bow (ft).
+    pub x: f64,
+    /// Distance from the centreline to starboard (ft), negative to port.
+    pub y: f64,
+    /// Side of the ship.
+    pub side: Side,
+    /// Height of the mount: -1 in the hull, 0 on deck, 1 raised and 2
+    /// double raised.
+    pub level: i32,
+    /// Radius of the mount (ft).
+    pub radius: f64,
+    /// Bearings the mount can train on, ignoring obstructions.
+    pub arc: (f64, f64),
+    /// Sectors blocked by the superstructure and by other mounts.
+    pub blocked: Vec<(f64, f64)>,
+}
+
+impl Mount { // {{{2
+    /// Half the length of the superstructure as a fraction of waterline
+    /// length.
+    ///
+    const SUPER_LEN: f64 = 0.1;
+
+    /// Half the width of the superstructure as a fraction of beam.
+    ///
+    const SUPER_WIDTH: f64 = 0.25;
+
+    /// Distance of side mounts from the centreline as a fraction of beam.
+    ///
+    const SIDE_WIDTH: f64 = 0.35;
+
+    /// Fraction of waterline length at each end that cannot hold a mount.
+    ///
+    const END_LEN: f64 = 0.05;
+
+    /// Angle from the beam that hull mounts can train through (degrees).
+    ///
+    const HULL_ARC: f64 = 60.0;
+
+    // place {{{3
+    /// Place every mount of every battery on the ship and work out the
+    /// sectors blocked by the superstructure and by other mounts.
+    ///
+    /// The superstructure stands at the break between the forward and aft
+    /// decks. Mounts of a group are spread along the part of the deck given
+    /// by its distribution, with the raised mounts closest to the
+    /// superstructure so that they fire over the others.
+    ///
+    pub fn place(ship: &Ship) -> Vec<Mount> {
+        let lwl = ship.hull.lwl();
+        let b = ship.hull.b;
+        let xs = Self::super_x(ship);
+
+        let mut mounts: Vec<Mount> = Vec::new();
+        for (i, btry) in ship.batteries.iter().enumerate() {
+            if btry.num == 0 { continue; }
+
+            for g in btry.groups.iter() {
+                let mut stations = Self::stations(g, xs);
+                stations.sort_by(|a, b| (a.0 - xs).abs().total_cmp(&(b.0 - xs).abs()));
+
+                let levels = std::iter::repeat_n(if g.two_mounts_up { 2 } else { 1 }, g.above as usize)
+                    .chain(std::iter::repeat_n(0, g.on as usize))
+                    .chain(std::iter::repeat_n(-1, g.below as usize));
+
+                for ((x, side), level) in stations.into_iter().zip(levels) {
+                    let y = match side {
+                        Side::Centre    => 0.0,
+                        Side::Starboard => Self::SIDE_WIDTH * b,
+                        Side::Port      => -Self::SIDE_WIDTH * b,
+                    };
+
+                    mounts.push(Mount {
+                        battery: i,
+                        guns: g.layout.guns_per(),
+                        x: x * lwl,
+                        y,
+                        side,
+                        level,
+                        radius: g.diameter_calc(btry.diam) / 2.0,
+                        arc: Self::base_arc(side, level, x < xs),
+                        blocked: Vec::new(),
+                    });
+                }
+            }
+        }
+
+        // Superstructure corners
+        let corners = [
+            ((xs - Self::SUPER_LEN) * lwl, -Self::SUPER_WIDTH * b),
+            ((xs - Self::SUPER_LEN) * lwl,  Self::SUPER_WIDTH * b),
+            ((xs + Self::SUPER_LEN) * lwl, -Self::SUPER_WIDTH * b),
+            ((xs + Self::SUPER_LEN) * lwl,  Self::SUPER_WIDTH * b),
+        ];
+
+        let others = mounts.clone();
+        for m in mounts.iter_mut() {
+            // Hull mounts are only masked by other hull mounts
+            if m.level >= 0 {
+                m.blocked.push(m.sector(&corners));
+            }
+
+            for o in others.iter() {
+                if o.x == m.x && o.y == m.y { continue; }
+
+                let masks = if m.level < 0 { o.level < 0 } else { o.level >= m.level };
+                if masks { m.blocked.push(m.shadow(o)); }
+            }
+        }
+
+        mounts
+    }
+
+    // super_x {{{3
+    /// Position of the centre of the superstructure as a fraction of
+    /// waterline length from the bow.
+    ///
+    fn super_x(ship: &Ship) -> f64 {
+        (ship.hull.fc_len + ship.hull.fd_len).clamp(0.3, 0.7)
+    }
+
+    // super_ends {{{3
+    /// Distances of the forward and aft ends of the superstructure from the
+    /// bow (ft).
+    ///
+    pub(crate) fn super_ends(ship: &Ship) -> (f64, f64) {
+        let lwl = ship.hull.lwl();
+        let xs = Self::super_x(ship);
+
+        ((xs - Self::SUPER_LEN) * lwl, (xs + Self::SUPER_LEN) * lwl)
+    }
+
+    // stations {{{3
+    /// Positions of a group's mounts as fractions of waterline length from
+    /// the bow, with the side they are on.
+    ///
+    fn stations(g: &SubBattery, xs: f64) -> Vec<(f64, Side)> {
+        use GunDistributionType::*;
+
+        let n = g.num_mounts();
+        let sides = matches!(g.distribution,
+            SidesEven | SidesEndsFD | SidesEndsAD |
+            SidesFDFwd | SidesFD | SidesFDAft |
+            SidesADFwd | SidesAD | SidesADAft
+        );
+
+        // Side mounts go in pairs, one each side
+        let k = if sides { n.div_ceil(2) } else { n };
+
+        // Forward and aft decks clear of the ends and the superstructure
+        let (f0, f1) = (Self::END_LEN, xs - Self::SUPER_LEN);
+        let (a0, a1) = (xs + Self::SUPER_LEN, 1.0 - Self::END_LEN);
+        let third = |a: f64, b: f64, i: f64| (a + (b - a) * i / 3.0, a + (b - a) * (i + 1.0) / 3.0);
+
+        let fwd = g.distribution.mounts_fwd(k, xs);
+        let pos: Vec<f64> = match g.distribution {
+            CenterlineEven | SidesEven =>
+                [spread(fwd, f0, f1), spread(k - fwd, a0, a1)].concat(),
+            CenterlineEndsFD | CenterlineEndsAD | SidesEndsFD | SidesEndsAD =>
+                [spread(fwd, f0, (f0 + f1) / 2.0), spread(k - fwd, (a0 + a1) / 2.0, a1)].concat(),
+            CenterlineFDFwd | SidesFDFwd => { let (a, b) = third(f0, f1, 0.0); spread(k, a, b) },
+            CenterlineFD    | SidesFD    => spread(k, f0, f1),
+            CenterlineFDAft | SidesFDAft => { let (a, b) = third(f0, f1, 2.0); spread(k, a, b) },
+            CenterlineADFwd | SidesADFwd => { let (a, b) = third(a0, a1, 0.0); spread(k, a, b) },
+            CenterlineAD    | SidesAD    => spread(k, a0, a1),
+            CenterlineADAft | SidesADAft => { let (a, b) = third(a0, a1, 2.0); spread(k, a, b) },
+        };
+
+        if sides {
+            pos.iter()
+                .flat_map(|x| [(*x, Side::Starboard), (*x, Side::Port)])
+                .take(n as usize)
+                .collect()
+        } else {
+            pos.iter().map(|x| (*x, Side::Centre)).collect()
+        }
+    }
+
+    // base_arc {{{3
+    /// Bearings a mount can train on before any obstructions.
+    ///
+    fn base_arc(side: Side, level: i32, fwd: bool) -> (f64, f64) {
+        let h = Self::HULL_ARC;
+
+        match (side, level < 0) {
+            (Side::Centre, false)    => (0.0, 360.0),
+            (Side::Starboard, false) => (Bearing::AHEAD, Bearing::ASTERN),
+            (Side::Port, false)      => (Bearing::ASTERN, 360.0),
+
+            // Hull mounts fire through ports in the side, or as chasers
+            // through the bow or stern
+            (Side::Centre, true) if fwd => (360.0 - h / 2.0, 360.0 + h / 2.0),
+            (Side::Centre, true)        => (Bearing::ASTERN - h / 2.0, Bearing::ASTERN + h / 2.0),
+            (Side::Starboard, true)     => (Bearing::STARBOARD - h, Bearing::STARBOARD + h),
+            (Side::Port, true)          => (Bearing::PORT - h, Bearing::PORT + h),
+        }
+    }
+
+    // bearing_to {{{3
+    /// Bearing from the mount to a point on the ship.
+    ///
+    fn bearing_to(&self, x: f64, y: f64) -> f64 {
+        (y - self.y).atan2(self.x - x).to_degrees().rem_euclid(360.0)
+    }
+
+    // sector {{{3
+    /// Sector covered by a set of points as seen from the mount.
+    ///
+    fn sector(&self, points: &[(f64, f64)]) -> (f64, f64) {
+        let (cx, cy) = points.iter().fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+        let c = self.bearing_to(cx / points.len() as f64, cy / points.len() as f64);
+
+        let (lo, hi) = points.iter()
+            .map(|(x, y)| (self.bearing_to(*x, *y) - c + 180.0).rem_euclid(360.0) - 180.0)
+            .fold((0.0_f64, 0.0_f64), |(lo, hi), a| (lo.min(a), hi.max(a)));
+
+        ((c + lo).rem_euclid(360.0), (c + lo).rem_euclid(360.0) + hi - lo)
+    }
+
+    // shadow {{{3
+    /// Sector masked by another mount.
+    ///
+    fn shadow(&self, other: &Mount) -> (f64, f64) {
+        let dist = (other.x - self.x).hypot(other.y - self.y);
+        let half = if dist > other.radius { (other.radius / dist).asin().to_degrees() } else { 90.0 };
+        let c = self.bearing_to(other.x, other.y);
+
+        ((c - half).rem_euclid(360.0), (c - half).rem_euclid(360.0) + 2.0 * half)
+    }
+
+    // bears {{{3
+    /// True if the mount can fire on a bearing.
+    ///
+    pub fn bears(&self, bearing: f64) -> bool {
+        // Offset of the bearing clockwise from the start of a sector
+        let off = |from: f64| (bearing - from).rem_euclid(360.0);
+
+        off(self.arc.0) <= self.arc.1 - self.arc.0 &&
+            !self.blocked.iter().any(|(from, to)| off(*from) > 0.0 && off(*from) < to - from)
+    }
+
+    // free_arc {{{3
+    /// Degrees of bearing the mount can fire on, to the nearest degree.
+    ///
+    pub fn free_arc(&self) -> u32 {
+        (0..360).filter(|b| self.bears(*b as f64)).count() as u32
+    }
+}
+
+// spread {{{2
+/// Positions of n mounts spread evenly between a and b.
+///
+fn spread(n: u32, a: f64, b: f64) -> Vec<f64> {
+    (0..n).map(|i| a + (i as f64 + 0.5) / n as f64 * (b - a)).collect()
+}
+
+// Testing Mount {{{2
+#[cfg(test)]
+mod mount {
+    use super::*;
+    use crate::weapons::GunLayoutType;
+
+    fn get_ship(groups: &[(GunDistributionType, u32, u32, bool)]) -> Ship {
+        let mut ship = Ship::default();
+        ship.hull.set_lwl(500.0);
+        ship.hull.b = 50.0;
+        ship.hull.fc_len = 0.2;
+        ship.hull.fd_len = 0.3;
+        ship.hull.qd_len = 0.15;
+
+        let b = ship.add_battery();
+        b.diam = 12.0;
+        b.groups.clear();
+        for (distribution, above, on, two_mounts_up) in groups.iter() {
+            let g = b.add_group();
+            g.layout = GunLayoutType::Twin;
+            g.distribution = distribution.clone();
+            g.above = *above;
+            g.on = *on;
+            g.two_mounts_up = *two_mounts_up;
+        }
+        b.mount_num = b.group_mounts();
+        b.num = 2 * b.mount_num;
+
+        ship
+    }
+
+    // Test bearing {{{3
+    macro_rules! test_bearing {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, groups) = $value;
+
+                    let ship = get_ship(&groups);
+                    let (ahead, astern, port, starboard) = expected;
+
+                    assert_eq!(Bearing { ahead, astern, port, starboard }, Bearing::of(&Mount::place(&ship), 0));
+                }
+            )*
+        }
+    }
+
+    test_bearing! {
+        // name:                    ((ahead, astern, port, starboard), [(distribution, above, on, two_mounts_up)])
+        bearing_one_fwd:            ((2, 0, 2, 2), [(GunDistributionType::CenterlineFD, 0, 1, false)]),
+        bearing_fore_and_aft:       ((2, 2, 4, 4), [(GunDistributionType::CenterlineEndsFD, 0, 2, false)]),
+        bearing_two_fwd_level:      ((2, 0, 4, 4), [(GunDistributionType::CenterlineFD, 0, 2, false)]),
+        bearing_two_fwd_raised:     ((4, 0, 4, 4), [(GunDistributionType::CenterlineFD, 1, 1, false)]),
+        bearing_abxy:               ((4, 4, 8, 8), [(GunDistributionType::CenterlineEndsFD, 2, 2, false)]),
+        bearing_wing:               ((4, 4, 2, 2), [(GunDistributionType::SidesEven, 0, 2, false)]),
+        bearing_three_groups:       ((8, 6, 8, 8), [
+            (GunDistributionType::CenterlineFD, 1, 1, false),
+            (GunDistributionType::CenterlineAD, 0, 1, false),
+            (GunDistributionType::SidesEven, 0, 2, false),
+        ]),
+    }
+
+    // Test free_arc {{{3
+    macro_rules! test_free_arc {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (low, high, groups) = $value;
+
+                    let ship = get_ship(&groups);
+                    let mounts = Mount::place(&ship);
+
+                    for m in mounts.iter() {
+                        assert!(m.free_arc() >= low, "{} < {}", m.free_arc(), low);
+                        assert!(m.free_arc() <= high, "{} > {}", m.free_arc(), high);
+                    }
+                }
+            )*
+        }
+    }
+
+    test_free_arc! {
+        // name:            (low, high, [(distribution, above, on, two_mounts_up)])
+        free_arc_single:    (340, 345, [(GunDistributionType::CenterlineFD, 0, 1, false)]),
+        free_arc_wing:      (181, 181, [(GunDistributionType::SidesEven, 0, 2, false)]),
+        free_arc_pair:      (325, 345, [(GunDistributionType::CenterlineFD, 1, 1, false)]),
+    }
+
+    // Test hull_mount {{{3
+    #[test]
+    fn hull_mount() {
+        let mut ship = get_ship(&[(GunDistributionType::SidesEven, 0, 0, false)]);
+        ship.batteries[0].groups[0].below = 2;
+        ship.batteries[0].mount_num = 2;
+        ship.batteries[0].num = 4;
+
+        let mounts = Mount::place(&ship);
+        assert_eq!(2, mounts.len());
+        assert!(mounts.iter().all(|m| m.level < 0 && !m.bears(Bearing::AHEAD) && !m.bears(Bearing::ASTERN)));
+        assert_eq!(Bearing { ahead: 0, astern: 0, port: 2, starboard: 2 }, Bearing::of(&mounts, 0));
+    }
+}
@@ -67,6 +67,15 @@ impl Armor { // {{{2
     // XXX: I do not know what this does.
     pub const INCH: f64 = 0.0185; 
 
+    /// Charge (lbs) withstood per cubic foot of torpedo protection depth,
+    /// so that a 15 ft deep system holds about 700 lbs.
+    ///
+    const TDS_CHARGE: f64 = 0.2;
+
+    /// Extra depth (ft) each inch of bulge plating is worth.
+    ///
+    const BULGE_DEPTH_PER_INCH: f64 = 0.5;
+
     // wgt {{{3
     /// Total weight of armor.
     ///
@@ -95,6 +104,48 @@ impl Armor { // {{{2
         self.main.len / (lwl * 0.65)
     }
 
+    // tds_depth {{{3
+    /// Depth of the torpedo protection on each side, from the outer hull to
+    /// the holding bulkhead, or to the original hull if there is only a
+    /// bulge.
+    ///
+    pub fn tds_depth(&self, b: f64, bb: f64) -> f64 {
+        let depth = if self.bulkhead.thick > 0.0 && self.bh_beam > 0.0 {
+            (bb - self.bh_beam) / 2.0
+        } else {
+            (bb - b) / 2.0
+        };
+
+        depth.max(0.0)
+    }
+
+    // tds_charge {{{3
+    /// Charge (lbs) the torpedo protection on each side can withstand
+    /// without flooding the vitals.
+    ///
+    /// The charge grows with the cube of the depth of the system. Each inch
+    /// of holding bulkhead or bulge plating counts as extra depth.
+    ///
+    pub fn tds_charge(&self, b: f64, bb: f64) -> f64 {
+        let depth = self.tds_depth(b, bb);
+        if depth <= 0.0 { return 0.0; }
+
+        let depth = depth +
+            self.bulkhead.thick * self.bh_kind.depth_per_inch() +
+            self.bulge.thick * Self::BULGE_DEPTH_PER_INCH;
+
+        Self::TDS_CHARGE * depth.powf(3.0)
+    }
+
+    // tds_coverage {{{3
+    /// Percentage of the "vital areas" covered by the torpedo protection.
+    ///
+    pub fn tds_coverage(&self, lwl: f64) -> f64 {
+        let len = if self.bulkhead.thick > 0.0 { self.bulkhead.len } else { self.bulge.len };
+
+        len / (lwl * 0.65)
+    }
+
     // max_hgt {{{3
     /// Maximum allowable belt height.
     ///
@@ -171,6 +222,35 @@ mod armor {
         max_belt_hgt_0: (20.02, 0.0),
         max_belt_hgt_45: (28.3, 45.0),
     }
+
+    // Test tds_charge {{{3
+    macro_rules! test_tds_charge {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, b, bb, bulge, thick, bh_beam, kind) = $value;
+
+                    let mut armor = Armor::default();
+                    armor.bulge.thick = bulge;
+                    armor.bulkhead.thick = thick;
+                    armor.bh_beam = bh_beam;
+                    armor.bh_kind = kind;
+
+                    assert_eq!(expected, to_place(armor.tds_charge(b, bb), 1));
+                }
+            )*
+        }
+    }
+    test_tds_charge! {
+        // name:          (charge, b, bb, bulge, bulkhead, bh_beam, bh_kind)
+        tds_none:         (0.0, 100.0, 100.0, 0.0, 0.0, 0.0, BulkheadType::Additional),
+        tds_bulge:        (25.0, 100.0, 110.0, 0.0, 0.0, 0.0, BulkheadType::Additional),
+        tds_bulge_plated: (43.2, 100.0, 110.0, 2.0, 0.0, 0.0, BulkheadType::Additional),
+        tds_additional:   (675.0, 100.0, 100.0, 0.0, 2.0, 76.0, BulkheadType::Additional),
+        tds_strengthened: (492.1, 100.0, 100.0, 0.0, 2.0, 76.0, BulkheadType::Strengthened),
+        tds_both:         (1166.4, 100.0, 110.0, 0.0, 2.0, 80.0, BulkheadType::Additional),
+    }
 }
 
 // Belt {{{1
@@ -271,6 +351,19 @@ pub enum BulkheadType {
     Additional,
 }
 
+impl BulkheadType { // {{{2
+    // depth_per_inch {{{3
+    /// Extra depth (ft) of torpedo protection each inch of holding
+    /// bulkhead is worth.
+    ///
+    pub fn depth_per_inch(&self) -> f64 {
+        match self {
+            Self::Strengthened => 0.75,
+            Self::Additional   => 1.5,
+        }
+    }
+}
+
 // BeltType {{{1
 /// Values for Belt::kind
 ///
@@ -0,0 +1,155 @@
+use serde::{Serialize, Deserialize};
+
+// Aviation {{{1
+/// Aircraft carried and the catapults, cranes, hangar and flight deck that
+/// handle them.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Aviation {
+    /// Number of catapults.
+    pub catapults: u32,
+    /// Number of cranes to recover seaplanes.
+    pub cranes: u32,
+    /// Hangar volume (cu ft).
+    pub hangar: f64,
+    /// Flight deck length (ft).
+    pub deck_len: f64,
+    /// Flight deck width (ft).
+    pub deck_wid: f64,
+    /// Number of aircraft.
+    pub aircraft: u32,
+    /// Weight of each aircraft (lbs).
+    pub aircraft_wgt: f64,
+    /// Aviation gasoline (t).
+    pub avgas: f64,
+}
+
+impl Aviation { // {{{2
+    /// Weight of a catapult (t).
+    ///
+    const CATAPULT_WGT: f64 = 15.0;
+
+    /// Deck area taken up by a catapult (sq ft).
+    ///
+    const CATAPULT_AREA: f64 = 1_000.0;
+
+    /// Weight of a crane (t).
+    ///
+    const CRANE_WGT: f64 = 8.0;
+
+    /// Deck area taken up by a crane (sq ft).
+    ///
+    const CRANE_AREA: f64 = 200.0;
+
+    /// Weight of hangar structure per cubic foot (t).
+    ///
+    const HANGAR_WGT: f64 = 0.0015;
+
+    /// Clear height of a hangar (ft).
+    ///
+    const HANGAR_HGT: f64 = 18.0;
+
+    /// Weight of flight deck plating and supports (lbs/sq ft).
+    ///
+    const DECK_WGT: f64 = 20.0;
+
+    /// Widest a flight deck can overhang the sides, as a multiple of beam.
+    ///
+    const OVERHANG: f64 = 1.25;
+
+    // is_empty {{{3
+    /// Whether the ship carries no aviation at all.
+    ///
+    pub fn is_empty(&self) -> bool {
+        self.catapults == 0 && self.cranes == 0 && self.hangar <= 0.0 &&
+            self.deck_len * self.deck_wid <= 0.0 && self.aircraft == 0 && self.avgas <= 0.0
+    }
+
+    // wgt_aircraft {{{3
+    /// Weight of the aircraft (t).
+    ///
+    pub fn wgt_aircraft(&self) -> f64 {
+        self.aircraft as f64 * self.aircraft_wgt / 2240.0
+    }
+
+    // wgt_deck {{{3
+    /// Weight of the flight deck (t).
+    ///
+    pub fn wgt_deck(&self) -> f64 {
+        (self.deck_len * self.deck_wid).max(0.0) * Self::DECK_WGT / 2240.0
+    }
+
+    // wgt {{{3
+    /// Weight of the aircraft and their catapults, cranes, hangar and flight
+    /// deck (t), excluding the gasoline.
+    ///
+    pub fn wgt(&self) -> f64 {
+        self.catapults as f64 * Self::CATAPULT_WGT +
+            self.cranes as f64 * Self::CRANE_WGT +
+            self.hangar.max(0.0) * Self::HANGAR_WGT +
+            self.wgt_deck() +
+            self.wgt_aircraft()
+    }
+
+    // deck_space {{{3
+    /// Deck area taken up by catapults, cranes and the hangar (sq ft).
+    ///
+    pub fn deck_space(&self) -> f64 {
+        self.catapults as f64 * Self::CATAPULT_AREA +
+            self.cranes as f64 * Self::CRANE_AREA +
+            self.hangar.max(0.0) / Self::HANGAR_HGT
+    }
+
+    // deck_fits {{{3
+    /// Whether the flight deck fits on a hull of a given overall length
+    /// and beam.
+    ///
+    pub fn deck_fits(&self, loa: f64, b: f64) -> bool {
+        self.deck_len <= loa && self.deck_wid <= b * Self::OVERHANG
+    }
+}
+
+// Testing Aviation {{{2
+#[cfg(test)]
+mod aircraft {
+    use super::*;
+    use crate::test_support::*;
+
+    // Test aviation {{{3
+    macro_rules! test_aviation {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (wgt, space, fits, avn) = $value;
+
+                    // Hull of 800 ft overall x 90 ft
+                    assert_eq!(wgt, to_place(avn.wgt(), 1));
+                    assert_eq!(space, to_place(avn.deck_space(), 0));
+                    assert_eq!(fits, avn.deck_fits(800.0, 90.0));
+                }
+            )*
+        }
+    }
+    test_aviation! {
+        // name:        (wgt, deck space, fits, aviation)
+        avn_none:       (0.0, 0.0, true, Aviation::default()),
+        avn_cruiser:    (86.7, 3511.0, true, Aviation {
+            catapults: 2, cranes: 2, hangar: 20_000.0, aircraft: 4, aircraft_wgt: 6_000.0, ..Default::default()
+        }),
+        avn_carrier:    (1108.9, 8333.0, true, Aviation {
+            hangar: 150_000.0, deck_len: 750.0, deck_wid: 100.0, aircraft: 60, aircraft_wgt: 8_000.0,
+            ..Default::default()
+        }),
+        avn_too_long:   (607.1, 0.0, false, Aviation { deck_len: 850.0, deck_wid: 80.0, ..Default::default() }),
+        avn_too_wide:   (750.0, 0.0, false, Aviation { deck_len: 700.0, deck_wid: 120.0, ..Default::default() }),
+    }
+
+    // Test is_empty {{{3
+    #[test]
+    fn is_empty() {
+        assert!(Aviation::default().is_empty());
+        assert!(!Aviation { avgas: 50.0, ..Default::default() }.is_empty());
+        assert!(!Aviation { catapults: 1, ..Default::default() }.is_empty());
+    }
+}
@@ -0,0 +1,554 @@
+use crate::{Ship, Armor};
+use crate::weapons::{Battery, GunType, ShellType};
+
+use format_num::format_num;
+
+use std::error::Error;
+
+// Gun {{{1
+/// The characteristics of a gun needed for exterior and terminal ballistics.
+///
+#[derive(Clone, Debug)]
+pub struct Gun {
+    /// Gun barrel diameter in inches.
+    pub diam: f64,
+    /// Gun barrel length in calibers.
+    pub len: f64,
+    /// Weight of each shell (lbs).
+    pub shell_wgt: f64,
+    /// Year gun was designed.
+    pub year: u32,
+    /// Type of gun.
+    pub kind: GunType,
+    /// Maximum elevation of the mount (degrees).
+    pub max_elevation: f64,
+    /// Type of shell fired.
+    pub shell: ShellType,
+}
+
+impl From<&Battery> for Gun { // {{{2
+    fn from(battery: &Battery) -> Self {
+        Self {
+            diam: battery.diam,
+            len: battery.len,
+            shell_wgt: battery.shell_wgt(),
+            year: battery.year,
+            kind: battery.kind.clone(),
+            max_elevation: battery.max_elevation,
+            shell: ShellType::AP,
+        }
+    }
+}
+
+impl Gun { // {{{2
+    /// Armor quality constant for de Marre's formula, calibrated for
+    /// Krupp cemented armor.
+    const DE_MARRE_K: f64 = 2_200.0;
+
+    /// Drag coefficient of a shell, averaged over its flight.
+    const DRAG: f64 = 0.3;
+
+    /// Sea level air density (kg/m³) and its scale height (m).
+    const AIR_DENSITY: f64 = 1.225;
+    const AIR_HEIGHT: f64 = 8_000.0;
+
+    /// Time step used when integrating a trajectory (s).
+    const DT: f64 = 0.02;
+
+    /// Interval between elevations in the range table (degrees).
+    const ELEVATION_STEP: f64 = 0.1;
+
+    /// Interval between ranges when searching for the immune zone (yds).
+    const RANGE_STEP: f64 = 100.0;
+
+    // Unit conversions
+    const FPS2MPS: f64 = 0.3048;
+    const LBS2KG: f64 = 0.45359237;
+    const IN2M: f64 = 0.0254;
+    const YDS2M: f64 = 0.9144;
+
+    // new {{{3
+    /// A breech loading gun with an estimated shell weight.
+    ///
+    pub fn new(diam: f64, len: f64, year: u32) -> Self {
+        let mut battery = Battery::default();
+        battery.diam = diam;
+        battery.len = len;
+        battery.year = year;
+
+        Self::from(&battery)
+    }
+
+    // from_ship {{{3
+    /// The gun of one of a ship's batteries.
+    ///
+    pub fn from_ship(ship: &Ship, battery: usize) -> Result<Self, Box<dyn Error>> {
+        match ship.batteries.get(battery) {
+            Some(b) if b.diam > 0.0 => Ok(Self::from(b)),
+            _ => Err(format!("{} has no battery {}", ship.name, battery).into()),
+        }
+    }
+
+    // with_shell {{{3
+    /// The same gun firing another type of shell.
+    ///
+    pub fn with_shell(mut self, shell: ShellType) -> Self {
+        self.shell_wgt *= shell.wgt_ratio() / self.shell.wgt_ratio();
+        self.shell = shell;
+
+        self
+    }
+
+    // velocity {{{3
+    /// Estimated muzzle velocity (ft/s) from barrel length.
+    ///
+    /// Guns before 1890 fired slower burning black and brown powders and
+    /// muzzle loaders lose velocity to windage.
+    ///
+    pub fn velocity(&self) -> f64 {
+        let v = 1_400.0 + 28.0 * self.len.clamp(10.0, 70.0);
+
+        let v = v * if self.year < 1890 { 0.85 } else { 1.0 };
+
+        v * match self.kind {
+            GunType::MuzzleLoading => 0.9,
+            _                      => 1.0,
+        }
+    }
+
+    // penetration {{{3
+    /// Thickness of armor (in) perforated at normal impact at a striking
+    /// velocity in ft/s (de Marre), reduced for shells other than AP.
+    ///
+    pub fn penetration(&self, v: f64) -> f64 {
+        if self.diam <= 0.0 || v <= 0.0 { return 0.0; } // catch divide by zero
+
+        let w = self.shell_wgt * Self::LBS2KG;
+        let v = v * Self::FPS2MPS;
+        let d = self.diam * Self::IN2M * 10.0; // decimetres
+
+        let t = (w.sqrt() * v / (Self::DE_MARRE_K * d.powf(0.75))).powf(1.0 / 0.7);
+
+        t / (Self::IN2M * 10.0) * self.shell.pen_factor()
+    }
+
+    // trajectory {{{3
+    /// Fire a shell at a given elevation and return where it lands.
+    ///
+    pub fn trajectory(&self, elevation: f64) -> Shot {
+        let v0 = self.velocity() * Self::FPS2MPS;
+        let m = self.shell_wgt * Self::LBS2KG;
+        let area = std::f64::consts::PI * (self.diam * Self::IN2M / 2.0).powf(2.0);
+        let k = if m > 0.0 { Self::DRAG * area / (2.0 * m) } else { 0.0 };
+
+        let (mut x, mut y, mut t) = (0.0, 0.0, 0.0);
+        let mut vx = v0 * elevation.to_radians().cos();
+        let mut vy = v0 * elevation.to_radians().sin();
+
+        loop {
+            let (px, py) = (x, y);
+
+            let v = (vx * vx + vy * vy).sqrt();
+            let drag = k * Self::AIR_DENSITY * (-y / Self::AIR_HEIGHT).exp() * v;
+
+            vx -= drag * vx * Self::DT;
+            vy -= (drag * vy + 9.80665) * Self::DT;
+            x += vx * Self::DT;
+            y += vy * Self::DT;
+            t += Self::DT;
+
+            if y < 0.0 {
+                // Interpolate back to the water
+                let f = if py - y > 0.0 { py / (py - y) } else { 0.0 };
+                x = px + (x - px) * f;
+                t = t - Self::DT + Self::DT * f;
+                break;
+            }
+        }
+
+        Shot {
+            elevation,
+            range: x / Self::YDS2M,
+            time: t,
+            velocity: (vx * vx + vy * vy).sqrt() / Self::FPS2MPS,
+            angle: (-vy).atan2(vx).to_degrees(),
+        }
+    }
+
+    // range_table {{{3
+    /// Trajectories at each elevation up to the maximum elevation in steps
+    /// of ELEVATION_STEP, stopping if range begins to fall. The first entry
+    /// is the gun's muzzle.
+    ///
+    pub fn range_table(&self) -> Vec<Shot> {
+        let mut table: Vec<Shot> = Vec::new();
+        if self.diam <= 0.0 { return table; }
+
+        table.push(Shot { velocity: self.velocity(), ..Default::default() });
+
+        let steps = (self.max_elevation / Self::ELEVATION_STEP).round() as u32;
+        for i in 1..=steps {
+            let shot = self.trajectory(i as f64 * Self::ELEVATION_STEP);
+
+            if table.last().is_some_and(|last| shot.range <= last.range) { break; }
+            table.push(shot);
+        }
+
+        table
+    }
+
+    // max_range {{{3
+    /// Maximum range (yds).
+    ///
+    pub fn max_range(&self) -> f64 {
+        match self.range_table().last() {
+            Some(shot) => shot.range,
+            None       => 0.0,
+        }
+    }
+
+    // at_range {{{3
+    /// Interpolate the shot that lands at a given range (yds) from a
+    /// range_table(). Return None if the range is beyond the table.
+    ///
+    pub fn at_range(table: &[Shot], range: f64) -> Option<Shot> {
+        let first = table.first()?;
+        if range <= first.range {
+            let f = if first.range > 0.0 { range / first.range } else { 0.0 };
+            return Some(Shot {
+                elevation: first.elevation * f,
+                range,
+                time: first.time * f,
+                velocity: first.velocity,
+                angle: first.angle * f,
+            });
+        }
+
+        let i = table.iter().position(|s| s.range >= range)?;
+        let (a, b) = (&table[i - 1], &table[i]);
+        let f = (range - a.range) / (b.range - a.range);
+
+        Some(Shot {
+            elevation: a.elevation + (b.elevation - a.elevation) * f,
+            range,
+            time: a.time + (b.time - a.time) * f,
+            velocity: a.velocity + (b.velocity - a.velocity) * f,
+            angle: a.angle + (b.angle - a.angle) * f,
+        })
+    }
+
+    // belt_pen {{{3
+    /// Thickness of belt armor (in) perforated by a shot. A positive belt
+    /// incline (top of the belt inboard) adds to the angle of fall.
+    ///
+    pub fn belt_pen(&self, shot: &Shot, incline: f64) -> f64 {
+        self.penetration(shot.velocity) * (shot.angle + incline).to_radians().cos().max(0.0)
+    }
+
+    // deck_pen {{{3
+    /// Thickness of deck armor (in) perforated by a shot.
+    ///
+    pub fn deck_pen(&self, shot: &Shot) -> f64 {
+        self.penetration(shot.velocity) * shot.angle.to_radians().sin().max(0.0)
+    }
+
+    // immune_zone {{{3
+    /// Band of ranges (yds) in which neither the main belt nor the main deck
+    /// is perforated. The outer edge is the maximum range if the deck is
+    /// never perforated. Return None if there is no immune zone.
+    ///
+    pub fn immune_zone(&self, armor: &Armor) -> Option<(f64, f64)> {
+        let table = self.range_table();
+        let max = table.last()?.range;
+
+        let mut zone: Option<(f64, f64)> = None;
+        let mut range = Self::RANGE_STEP;
+        while range <= max {
+            let shot = Self::at_range(&table, range)?;
+
+            let immune =
+                self.belt_pen(&shot, armor.incline) < armor.main.thick &&
+                self.deck_pen(&shot) < armor.deck.md;
+
+            match (immune, zone) {
+                (true, None)              => zone = Some((range, range)),
+                (true, Some((inner, _)))  => zone = Some((inner, range)),
+                (false, Some(_))          => break,
+                (false, None)             => (),
+            }
+
+            range += Self::RANGE_STEP;
+        }
+
+        zone
+    }
+
+    // range_report {{{3
+    /// Print the range table at each whole degree of elevation.
+    ///
+    pub fn range_report(&self) -> String {
+        let mut r: Vec<String> = Vec::new();
+        let table = self.range_table();
+
+        r.push(format!("{:.2}\" / {:.1} cal gun, {:.0} lbs {} shell, {} ft/s muzzle velocity, {:.0} degrees maximum elevation",
+            self.diam, self.len, self.shell_wgt, self.shell, format_num!(",.0", self.velocity()), self.max_elevation));
+        r.push("".into());
+
+        r.push(format!("{:>9} {:>8} {:>8} {:>10} {:>8} {:>10} {:>10}",
+            "Elevation", "Range", "Time", "Velocity", "Fall", "Belt pen", "Deck pen"));
+        r.push(format!("{:>9} {:>8} {:>8} {:>10} {:>8} {:>10} {:>10}",
+            "(deg)", "(yds)", "(s)", "(ft/s)", "(deg)", "(in)", "(in)"));
+
+        let per_degree = (1.0 / Self::ELEVATION_STEP).round() as usize;
+        for shot in table.iter().skip(per_degree).step_by(per_degree) {
+            r.push(format!("{:>9.1} {:>8} {:>8.1} {:>10} {:>8.1} {:>10.2} {:>10.2}",
+                shot.elevation,
+                format_num!(",.0", shot.range),
+                shot.time,
+                format_num!(",.0", shot.velocity),
+                shot.angle,
+                self.belt_pen(shot, 0.0),
+                self.deck_pen(shot),
+            ));
+        }
+
+        r.join("\n")
+    }
+
+    // iz_report {{{3
+    /// Print the penetration of the gun against range and the immune zone
+    /// of a target ship.
+    ///
+    pub fn iz_report(&self, target: &Ship) -> String {
+        let mut r: Vec<String> = Vec::new();
+        let table = self.range_table();
+        let max = match table.last() { Some(shot) => shot.range, None => 0.0 };
+
+        r.push(format!("{:.2}\" / {:.1} cal gun, {:.0} lbs {} shell, {} ft/s muzzle velocity",
+            self.diam, self.len, self.shell_wgt, self.shell, format_num!(",.0", self.velocity())));
+        r.push(format!("Maximum range {} yds at {:.1} degrees elevation",
+            format_num!(",.0", max), self.max_elevation));
+        r.push("".into());
+
+        r.push(format!("{:>8} {:>10} {:>8} {:>10} {:>10}",
+            "Range", "Velocity", "Fall", "Belt pen", "Deck pen"));
+        r.push(format!("{:>8} {:>10} {:>8} {:>10} {:>10}",
+            "(yds)", "(ft/s)", "(deg)", "(in)", "(in)"));
+
+        let mut range = 0.0;
+        while range <= max {
+            if let Some(shot) = Self::at_range(&table, range) {
+                r.push(format!("{:>8} {:>10} {:>8.1} {:>10.2} {:>10.2}",
+                    format_num!(",.0", range),
+                    format_num!(",.0", shot.velocity),
+                    shot.angle,
+                    self.belt_pen(&shot, target.armor.incline),
+                    self.deck_pen(&shot),
+                ));
+            }
+            range += 2_000.0;
+        }
+        r.push("".into());
+
+        r.push(format!("{}: {:.2}\" belt inclined {:.1} degrees, {:.2}\" deck",
+            target.name, target.armor.main.thick, target.armor.incline, target.armor.deck.md));
+        r.push(match self.immune_zone(&target.armor) {
+            Some((inner, outer)) => format!("Immune zone {} - {} yds{}",
+                format_num!(",.0", inner),
+                format_num!(",.0", outer),
+                if outer + Self::RANGE_STEP > max { " (maximum range)" } else { "" }
+            ),
+            None => "No immune zone".into(),
+        });
+
+        r.join("\n")
+    }
+}
+
+// Shot {{{1
+/// Where and how a shell fired at a given elevation lands.
+///
+#[derive(Clone, Debug, Default)]
+pub struct Shot {
+    /// Elevation of the gun (degrees).
+    pub elevation: f64,
+    /// Range (yds).
+    pub range: f64,
+    /// Time of flight (s).
+    pub time: f64,
+    /// Striking velocity (ft/s).
+    pub velocity: f64,
+    /// Angle of fall below horizontal (degrees).
+    pub angle: f64,
+}
+
+// Testing Gun {{{2
+#[cfg(test)]
+mod gun {
+    use super::*;
+    use crate::test_support::*;
+
+    fn get_gun(diam: f64, len: f64, shell_wgt: f64) -> Gun {
+        Gun {
+            diam,
+            len,
+            shell_wgt,
+            year: 1920,
+            kind: GunType::BreechLoading,
+            max_elevation: Battery::ELEVATION,
+            shell: ShellType::AP,
+        }
+    }
+
+    // Test velocity {{{3
+    macro_rules! test_velocity {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, len, year, kind) = $value;
+
+                    let mut gun = get_gun(12.0, len, 850.0);
+                    gun.year = year;
+                    gun.kind = kind;
+
+                    assert_eq!(expected, to_place(gun.velocity(), 2));
+                }
+            )*
+        }
+    }
+    test_velocity! {
+        // name:             (velocity, len, year, kind)
+        velocity_45:         (2660.0, 45.0, 1920, GunType::BreechLoading),
+        velocity_short:      (1680.0, 5.0, 1920, GunType::BreechLoading),
+        velocity_early:      (1904.0, 30.0, 1885, GunType::BreechLoading),
+        velocity_ml:         (1713.6, 30.0, 1885, GunType::MuzzleLoading),
+    }
+
+    // Test penetration {{{3
+    macro_rules! test_penetration {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, diam, shell_wgt, v) = $value;
+
+                    let gun = get_gun(diam, 45.0, shell_wgt);
+
+                    assert_eq!(expected, to_place(gun.penetration(v), 1));
+                }
+            )*
+        }
+    }
+    test_penetration! {
+        // name:              (pen, diam, shell_wgt, v)
+        penetration_16:       (29.0, 16.0, 2240.0, 2625.0),
+        penetration_6:        (10.2, 6.0, 105.0, 2800.0),
+        penetration_stopped:  (0.0, 16.0, 2240.0, 0.0),
+        penetration_no_gun:   (0.0, 0.0, 0.0, 2625.0),
+    }
+
+    // Test with_shell {{{3
+    macro_rules! test_with_shell {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (pen, shell_wgt, shell) = $value;
+
+                    let gun = get_gun(16.0, 45.0, 2240.0).with_shell(ShellType::HE).with_shell(shell);
+
+                    assert_eq!(shell_wgt, to_place(gun.shell_wgt, 1));
+                    assert_eq!(pen, to_place(gun.penetration(2625.0), 1));
+                }
+            )*
+        }
+    }
+    test_with_shell! {
+        // name:              (pen, shell_wgt, shell)
+        with_shell_ap:        (29.0, 2240.0, ShellType::AP),
+        with_shell_he:        (7.1, 1680.0, ShellType::HE),
+        with_shell_shrapnel:  (5.0, 1792.0, ShellType::Shrapnel),
+    }
+
+    // Test range_table {{{3
+    #[test]
+    fn range_table_increases() {
+        let gun = get_gun(16.0, 45.0, 2240.0);
+        let table = gun.range_table();
+
+        assert_eq!(301, table.len());
+        assert!(table.windows(2).all(|w|
+            w[0].range < w[1].range && w[0].angle < w[1].angle && w[0].time < w[1].time
+        ));
+        assert!(table.iter().skip(1).all(|s| s.velocity < gun.velocity()));
+    }
+
+    // Test at_range {{{3
+    macro_rules! test_at_range {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (found, range) = $value;
+
+                    let gun = get_gun(16.0, 45.0, 2240.0);
+                    let table = gun.range_table();
+                    let shot = Gun::at_range(&table, range);
+
+                    assert_eq!(found, shot.is_some());
+                    if let Some(shot) = shot {
+                        assert_eq!(to_place(range, 3), to_place(shot.range, 3));
+                    }
+                }
+            )*
+        }
+    }
+    test_at_range! {
+        // name:            (found, range)
+        at_range_zero:      (true, 0.0),
+        at_range_mid:       (true, 20_000.0),
+        at_range_too_far:   (false, 100_000.0),
+    }
+
+    // Test immune_zone {{{3
+    macro_rules! test_immune_zone {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, belt, deck) = $value;
+
+                    let gun = get_gun(16.0, 45.0, 2240.0);
+                    let mut armor = Armor::default();
+                    armor.main.thick = belt;
+                    armor.deck.md = deck;
+
+                    let zone = gun.immune_zone(&armor);
+                    assert_eq!(expected, zone.is_some());
+
+                    if let Some((inner, outer)) = zone {
+                        let table = gun.range_table();
+                        let at = |range| Gun::at_range(&table, range).unwrap();
+
+                        assert!(gun.belt_pen(&at(inner), 0.0) < belt);
+                        assert!(gun.deck_pen(&at(outer)) < deck);
+
+                        // The zone is no wider than it should be
+                        assert!(gun.belt_pen(&at(inner - Gun::RANGE_STEP), 0.0) >= belt);
+                        if outer + Gun::RANGE_STEP <= gun.max_range() {
+                            assert!(gun.deck_pen(&at(outer + Gun::RANGE_STEP)) >= deck);
+                        }
+                    }
+                }
+            )*
+        }
+    }
+    test_immune_zone! {
+        // name:             (zone, belt, deck)
+        immune_zone_battle:  (true, 16.0, 7.0),
+        immune_zone_max:     (true, 16.0, 20.0),
+        immune_zone_thin:    (false, 6.0, 2.0),
+        immune_zone_none:    (false, 0.0, 0.0),
+    }
+}
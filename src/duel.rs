@@ -0,0 +1,520 @@
+use crate::{Ship, Gun, Shot};
+
+use format_num::format_num;
+
+use std::fmt;
+
+// Rng {{{1
+/// A small seedable random number generator (SplitMix64) so that duels
+/// can be repeated exactly from a seed.
+///
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng { // {{{2
+    // new {{{3
+    /// Create a generator from a seed.
+    ///
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    // next_u64 {{{3
+    /// Next random integer.
+    ///
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e3779b97f4a7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+        z ^ (z >> 31)
+    }
+
+    // next_f64 {{{3
+    /// Next random number in the range [0, 1).
+    ///
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+}
+
+// Testing Rng {{{2
+#[cfg(test)]
+mod rng {
+    use super::*;
+
+    // Test repeatable {{{3
+    macro_rules! test_repeatable {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let seed = $value;
+
+                    let mut a = Rng::new(seed);
+                    let mut b = Rng::new(seed);
+                    let mut c = Rng::new(seed + 1);
+
+                    let a: Vec<f64> = (0..100).map(|_| a.next_f64()).collect();
+                    let b: Vec<f64> = (0..100).map(|_| b.next_f64()).collect();
+                    let c: Vec<f64> = (0..100).map(|_| c.next_f64()).collect();
+
+                    assert_eq!(a, b);
+                    assert_ne!(a, c);
+                    assert!(a.iter().all(|n| (0.0..1.0).contains(n)));
+                }
+            )*
+        }
+    }
+    test_repeatable! {
+        // name:            seed
+        repeatable_zero:    0,
+        repeatable_one:     1,
+        repeatable_big:     u64::MAX - 1,
+    }
+}
+
+// Outcome {{{1
+/// Result of a duel.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    /// The first ship sank the second.
+    A,
+    /// The second ship sank the first.
+    B,
+    /// Both ships sank in the same salvo.
+    Mutual,
+    /// Neither ship sank before the end of the duel.
+    Draw,
+}
+
+impl fmt::Display for Outcome { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::A      => "first ship wins",
+                Self::B      => "second ship wins",
+                Self::Mutual => "both ships sunk",
+                Self::Draw   => "draw",
+            }
+        )
+    }
+}
+
+// Hit {{{1
+/// A hit scored during a duel.
+///
+#[derive(Clone, Debug)]
+pub struct Hit {
+    /// Salvo in which the hit was scored.
+    pub turn: u32,
+    /// Index of the firing ship (0 or 1).
+    pub firer: usize,
+    /// Diameter of the shell (in).
+    pub diam: f64,
+    /// True if the shell struck the deck rather than the belt.
+    pub deck: bool,
+    /// Armor the shell could perforate (in).
+    pub pen: f64,
+    /// Armor struck (in).
+    pub armor: f64,
+    /// Damage done in units of the target's own main battery shell.
+    pub damage: f64,
+}
+
+impl Hit { // {{{2
+    // penetrated {{{3
+    /// True if the shell perforated the armor it struck.
+    ///
+    pub fn penetrated(&self) -> bool {
+        self.pen > self.armor
+    }
+}
+
+// Fight {{{1
+/// The course of a single duel.
+///
+#[derive(Clone, Debug)]
+pub struct Fight {
+    /// Result of the duel.
+    pub outcome: Outcome,
+    /// Number of salvos fired.
+    pub turns: u32,
+    /// Every hit scored, in order.
+    pub hits: Vec<Hit>,
+}
+
+// Side {{{1
+/// A battery able to fire in a duel.
+///
+struct Guns {
+    gun: Gun,
+    num: u32,
+    shot: Shot,
+    max_range: f64,
+}
+
+/// A ship's part in a duel, worked out once before any salvos are fired.
+///
+struct Side<'a> {
+    ship: &'a Ship,
+    guns: Vec<Guns>,
+    accuracy: f64,
+    target_factor: f64,
+    capacity: f64,
+    shell_wgt: f64,
+}
+
+impl<'a> Side<'a> { // {{{2
+    // new {{{3
+    /// Prepare a ship to fight at a given range.
+    ///
+    fn new(ship: &'a Ship, range: f64) -> Self {
+        let mut guns = Vec::new();
+        for b in ship.batteries.iter().filter(|b| b.num > 0 && b.diam > 0.0) {
+            let gun = Gun::from(b);
+            let table = gun.range_table();
+
+            if let (Some(shot), Some(last)) = (Gun::at_range(&table, range), table.last()) {
+                guns.push(Guns { gun, num: b.num, shot, max_range: last.range });
+            }
+        }
+
+        Self {
+            ship,
+            guns,
+            accuracy: (ship.steadiness() / 100.0).clamp(Duel::MIN_STEADINESS, 1.0),
+            target_factor: 1.0 / (1.0 + ship.engine.vmax / Duel::SPEED_FACTOR),
+            capacity: ship.damage_shell_num().max(1.0),
+            shell_wgt: match ship.batteries.first() {
+                Some(b) if b.diam > 0.0 => b.shell_wgt(),
+                _ => Gun::new(ship.damage_shell_size(), Duel::SHELL_LEN, ship.year).shell_wgt,
+            },
+        }
+    }
+}
+
+// Duel {{{1
+/// A gunnery duel between two ships at a fixed range. Each turn both ships
+/// fire every gun that can reach the other. The chance of each shell hitting
+/// depends on the firer's steadiness, the range and the target's speed.
+///
+/// Each hit does damage in proportion to its shell weight, measured in
+/// shells of the target's own main battery, so the weight of fire landing
+/// each turn is the firer's broadside weight (wgt_broad()) scaled by the
+/// chance of hitting. Hits that fail to perforate the belt or deck do a
+/// fraction of that. A ship sinks when its damage reaches
+/// damage_shell_num().
+///
+pub struct Duel<'a> {
+    /// First ship.
+    pub a: &'a Ship,
+    /// Second ship.
+    pub b: &'a Ship,
+    /// Range (yds).
+    pub range: f64,
+}
+
+impl<'a> Duel<'a> { // {{{2
+    /// Chance of a shell hitting from a perfectly steady ship at point blank
+    /// range against a stationary target.
+    const HIT_RATE: f64 = 0.25;
+
+    /// Lowest steadiness used when working out accuracy.
+    const MIN_STEADINESS: f64 = 0.1;
+
+    /// Speed (kts) at which a target is half as likely to be hit.
+    const SPEED_FACTOR: f64 = 30.0;
+
+    /// Fraction of a shell's damage done by a hit that fails to perforate.
+    const UNPENETRATED: f64 = 0.25;
+
+    /// Gun length (calibers) used to estimate the reference shell of a
+    /// target without a main battery.
+    const SHELL_LEN: f64 = 45.0;
+
+    /// Number of salvos before a duel is called a draw.
+    pub const MAX_TURNS: u32 = 400;
+
+    // new {{{3
+    /// Set up a duel.
+    ///
+    pub fn new(a: &'a Ship, b: &'a Ship, range: f64) -> Self {
+        Self { a, b, range }
+    }
+
+    // salvo {{{3
+    /// Fire every gun of one side at the other and return the hits.
+    ///
+    fn salvo(rng: &mut Rng, turn: u32, firer: usize, sides: &[Side; 2]) -> Vec<Hit> {
+        let (us, them) = (&sides[firer], &sides[1 - firer]);
+        let mut hits = Vec::new();
+
+        for g in us.guns.iter() {
+            let p = Self::HIT_RATE * us.accuracy * them.target_factor *
+                (1.0 - g.shot.range / g.max_range).max(0.0);
+
+            for _ in 0..g.num {
+                if rng.next_f64() >= p { continue; }
+
+                let deck = rng.next_f64() < g.shot.angle.to_radians().sin();
+                let (pen, armor) = if deck {
+                    (g.gun.deck_pen(&g.shot), them.ship.armor.deck.md)
+                } else {
+                    (g.gun.belt_pen(&g.shot, them.ship.armor.incline), them.ship.armor.main.thick)
+                };
+
+                let damage = g.gun.shell_wgt / them.shell_wgt *
+                    if pen > armor { 1.0 } else { Self::UNPENETRATED };
+
+                hits.push(Hit { turn, firer, diam: g.gun.diam, deck, pen, armor, damage });
+            }
+        }
+
+        hits
+    }
+
+    // fight {{{3
+    /// Fight a single duel.
+    ///
+    pub fn fight(&self, rng: &mut Rng) -> Fight {
+        let sides = [Side::new(self.a, self.range), Side::new(self.b, self.range)];
+        self.fight_sides(rng, &sides)
+    }
+
+    // fight_sides {{{3
+    /// Fight a single duel between prepared sides.
+    ///
+    fn fight_sides(&self, rng: &mut Rng, sides: &[Side; 2]) -> Fight {
+        let mut hits = Vec::new();
+        let mut damage = [0.0, 0.0];
+
+        for turn in 1..=Self::MAX_TURNS {
+            // Both ships fire before damage is applied
+            for firer in 0..2 {
+                for hit in Self::salvo(rng, turn, firer, sides) {
+                    damage[1 - hit.firer] += hit.damage;
+                    hits.push(hit);
+                }
+            }
+
+            let sunk = [damage[0] >= sides[0].capacity, damage[1] >= sides[1].capacity];
+            let outcome = match sunk {
+                [true, true]  => Some(Outcome::Mutual),
+                [false, true] => Some(Outcome::A),
+                [true, false] => Some(Outcome::B),
+                _             => None,
+            };
+
+            if let Some(outcome) = outcome {
+                return Fight { outcome, turns: turn, hits };
+            }
+        }
+
+        Fight { outcome: Outcome::Draw, turns: Self::MAX_TURNS, hits }
+    }
+
+    // fights {{{3
+    /// Fight a number of duels from one seed.
+    ///
+    pub fn fights(&self, seed: u64, runs: u32) -> Vec<Fight> {
+        let sides = [Side::new(self.a, self.range), Side::new(self.b, self.range)];
+        let mut rng = Rng::new(seed);
+
+        (0..runs).map(|_| self.fight_sides(&mut rng, &sides)).collect()
+    }
+
+    // report {{{3
+    /// Print the hit log of the first duel and the outcomes of all of them.
+    ///
+    pub fn report(&self, seed: u64, runs: u32) -> String {
+        let mut r: Vec<String> = Vec::new();
+        let names = [&self.a.name, &self.b.name];
+        let fights = self.fights(seed, runs.max(1));
+
+        r.push(format!("Duel at {} yds, seed {}", format_num!(",.0", self.range), seed));
+        r.push("".into());
+
+        for (i, ship) in [self.a, self.b].iter().enumerate() {
+            let side = Side::new(ship, self.range);
+            r.push(format!("{}: {} lbs broadside, {:.2}\" belt, {:.2}\" deck, {:.1} kts, steadiness {:.0}, sinks after {:.1} {:.1}\" hits",
+                names[i],
+                format_num!(",.0", ship.wgt_broad()),
+                ship.armor.main.thick,
+                ship.armor.deck.md,
+                ship.engine.vmax,
+                ship.steadiness(),
+                side.capacity,
+                ship.damage_shell_size(),
+            ));
+            if side.guns.is_empty() {
+                r.push(format!("{}: no guns in range", names[i]));
+            }
+        }
+        r.push("".into());
+
+        let first = &fights[0];
+        let mut damage = [0.0, 0.0];
+        for hit in first.hits.iter() {
+            let target = 1 - hit.firer;
+            damage[target] += hit.damage;
+
+            r.push(format!("Turn {:>3}: {} hits {} with {:.1}\" shell on {}, {} ({:.1}\" vs {:.1}\"), damage {:.1}/{:.1}",
+                hit.turn,
+                names[hit.firer],
+                names[target],
+                hit.diam,
+                if hit.deck { "deck" } else { "belt" },
+                if hit.penetrated() { "penetrates" } else { "fails to penetrate" },
+                hit.pen,
+                hit.armor,
+                damage[target],
+                [self.a, self.b][target].damage_shell_num(),
+            ));
+        }
+        r.push(format!("Result after {} turns: {}", first.turns,
+            match first.outcome {
+                Outcome::A => format!("{} wins", names[0]),
+                Outcome::B => format!("{} wins", names[1]),
+                outcome    => outcome.to_string(),
+            }
+        ));
+        r.push("".into());
+
+        let count = |o: Outcome| fights.iter().filter(|f| f.outcome == o).count() as f64;
+        let pct = |o: Outcome| format!("{:.1}%", count(o) / fights.len() as f64 * 100.0);
+
+        r.push(format!("Outcomes over {} runs:", fights.len()));
+        r.push(format!("    {} wins: {}", names[0], pct(Outcome::A)));
+        r.push(format!("    {} wins: {}", names[1], pct(Outcome::B)));
+        r.push(format!("    Both sunk: {}", pct(Outcome::Mutual)));
+        r.push(format!("    Draw: {}", pct(Outcome::Draw)));
+        r.push(format!("    Mean length: {:.1} turns",
+            fights.iter().map(|f| f.turns as f64).sum::<f64>() / fights.len() as f64));
+
+        r.join("\n")
+    }
+}
+
+// Testing Duel {{{2
+#[cfg(test)]
+mod gunnery_duel {
+    use super::*;
+    use crate::test_support::{self, get_sized_hull};
+
+    fn get_ship(name: &str, diam: f64, belt: f64) -> Ship {
+        let mut ship = test_support::get_ship(1920,
+            get_sized_hull(7_000.0, 500.0, 50.0, 18.0), (8, diam, 45.0, 4));
+
+        ship.name = name.into();
+        ship.engine.vmax = 25.0;
+        ship.engine.vcruise = 10.0;
+
+        ship.armor.main.thick = belt;
+        ship.armor.main.len = 300.0;
+        ship.armor.main.hgt = 12.0;
+        ship.armor.deck.md = 2.0;
+
+        ship
+    }
+
+    // Test seeded {{{3
+    macro_rules! test_seeded {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (seed, range) = $value;
+
+                    let a = get_ship("A", 8.0, 4.0);
+                    let b = get_ship("B", 8.0, 4.0);
+                    let duel = Duel::new(&a, &b, range);
+
+                    let first: Vec<u32> = duel.fights(seed, 10).iter().map(|f| f.turns).collect();
+                    let again: Vec<u32> = duel.fights(seed, 10).iter().map(|f| f.turns).collect();
+
+                    assert_eq!(first, again);
+                    assert_eq!(duel.report(seed, 10), duel.report(seed, 10));
+                }
+            )*
+        }
+    }
+    test_seeded! {
+        // name:          (seed, range)
+        seeded_close:     (1, 5_000.0),
+        seeded_long:      (42, 15_000.0),
+    }
+
+    // Test outcome {{{3
+    macro_rules! test_outcome {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, a_gun, b_gun, range) = $value;
+
+                    let a = get_ship("A", a_gun, 6.0);
+                    let b = get_ship("B", b_gun, 6.0);
+                    let duel = Duel::new(&a, &b, range);
+
+                    let fights = duel.fights(1, 100);
+                    let wins = fights.iter().filter(|f| f.outcome == expected).count();
+
+                    assert!(wins > 50, "{} wins", wins);
+                }
+            )*
+        }
+    }
+    test_outcome! {
+        // name:              (outcome, a_gun, b_gun, range)
+        outcome_a_bigger:     (Outcome::A, 12.0, 6.0, 10_000.0),
+        outcome_b_bigger:     (Outcome::B, 6.0, 12.0, 10_000.0),
+        outcome_out_of_range: (Outcome::Draw, 8.0, 8.0, 100_000.0),
+    }
+
+    // Test broadside {{{3
+    macro_rules! test_broadside {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (num, diam) = $value;
+
+                    let mut a = get_ship("A", 8.0, 6.0);
+                    let b = get_ship("B", 8.0, 6.0);
+
+                    a.add_battery();
+                    a.batteries[1].num = num;
+                    a.batteries[1].diam = diam;
+                    a.batteries[1].len = 45.0;
+                    a.batteries[1].mount_num = num;
+                    a.batteries[1].groups[0].on = num;
+
+                    // Mean damage done to B per turn
+                    let rate = |a: &Ship| {
+                        let fights = Duel::new(a, &b, 8_000.0).fights(1, 100);
+                        let damage: f64 = fights.iter()
+                            .flat_map(|f| f.hits.iter().filter(|h| h.firer == 0))
+                            .map(|h| h.damage)
+                            .sum();
+
+                        damage / fights.iter().map(|f| f.turns as f64).sum::<f64>()
+                    };
+
+                    let main = get_ship("A", 8.0, 6.0);
+                    assert!(a.wgt_broad() > main.wgt_broad());
+                    assert!(rate(&a) > rate(&main));
+                }
+            )*
+        }
+    }
+    test_broadside! {
+        // name:                (num, diam)
+        broadside_secondary:    (8, 6.0),
+        broadside_tertiary:     (12, 4.0),
+    }
+}
@@ -36,12 +36,23 @@ pub struct Engine {
 
     /// Percentage of bunker weight devoted to coal.
     pub pct_coal: f64,
+
+    /// Further machinery sets alongside the main plant.
+    #[serde(default)]
+    pub plants: Vec<Plant>,
 }
 
 impl Engine { // {{{2
     /// XXX: self.range is divided by this in bunker()
     const RANGE: f64 = 7000.0;
 
+    /// Number of halvings used to find the speed reached with a given
+    /// horsepower.
+    const ITERATIONS: u32 = 40;
+
+    /// Fastest speed speed_at() looks for (kts).
+    const SPEED_MAX: f64 = 60.0;
+
     // set_shafts {{{3
     /// Set the number of shafts in the engine and set any
     /// Hull parameters that depend on the number of shafts.
@@ -63,7 +74,7 @@ impl Engine { // {{{2
     // hp {{{3
     /// Horsepower required to achieve a given speed.
     ///
-    fn hp(&self, v: f64, d: f64, lwl: f64, leff: f64, cs: f64, ws: f64) -> f64 {
+    pub(crate) fn hp(&self, v: f64, d: f64, lwl: f64, leff: f64, cs: f64, ws: f64) -> f64 {
         let len_hp =
             if v <= 15.0 {
                 lwl - (leff - lwl) 
@@ -100,6 +111,31 @@ impl Engine { // {{{2
         self.hp(self.vcruise.min(self.vmax), d, lwl, leff, cs, ws)
     }
 
+    // hp_main {{{3
+    /// Horsepower of the main plant, the part of hp_max() not supplied by
+    /// plants that run with it at full power.
+    ///
+    pub fn hp_main(&self, d: f64, lwl: f64, leff: f64, cs: f64, ws: f64) -> f64 {
+        let others: f64 = self.plants.iter()
+            .filter(|p| p.role.at_full_power())
+            .fold(0.0, |hp, p| hp + p.hp);
+
+        (self.hp_max(d, lwl, leff, cs, ws) - others).max(0.0)
+    }
+
+    // speed_at {{{3
+    /// Speed reached with a given horsepower.
+    ///
+    pub fn speed_at(&self, hp: f64, d: f64, lwl: f64, leff: f64, cs: f64, ws: f64) -> f64 {
+        let (mut lo, mut hi) = (0.0, Self::SPEED_MAX);
+        for _ in 0..Self::ITERATIONS {
+            let v = (lo + hi) / 2.0;
+            if self.hp(v, d, lwl, leff, cs, ws) < hp { lo = v; } else { hi = v; }
+        }
+
+        (lo + hi) / 2.0
+    }
+
     // rf {{{3
     /// Friction resistance at a given speed.
     ///
@@ -170,17 +206,63 @@ impl Engine { // {{{2
     // bunker {{{3
     /// Bunkerage weight.
     ///
+    /// Fuel for the range at cruising speed is burnt by the plant of
+    /// cruise_plant() if there is one.
+    ///
     pub fn bunker(&self, d: f64, lwl: f64, leff: f64, cs: f64, ws: f64) -> f64 {
         if self.vcruise == 0.0 { return 0.0; } // catch divide by zero
 
-        let bunker = self.range as f64 / (1.0 + 0.4 * (1.0 - self.pct_coal as f64));
-        let bunker = bunker / self.boiler.bunker_factor(self.year);
+        let economy = match self.cruise_plant(d, lwl, leff, cs, ws) {
+            Some(plant) => plant.economy(),
+            None        => self.economy(),
+        };
+
+        let bunker = self.range as f64 / economy;
 
         bunker /
             (1.8 / self.hp_cruise(d, lwl, leff, cs, ws) * Self::RANGE as f64 * self.vcruise * 0.1) +
             d * 0.005
     }
 
+    // bunker_fuels {{{3
+    /// Bunkerage weight of each fuel at normal displacement.
+    ///
+    /// The main plant carries a reserve of its own fuel and the fuel for
+    /// the range at cruising speed is that of the plant that cruises.
+    ///
+    pub fn bunker_fuels(&self, d: f64, lwl: f64, leff: f64, cs: f64, ws: f64) -> Vec<(FuelType, f64)> {
+        let bunker = self.bunker(d, lwl, leff, cs, ws);
+
+        let mut fuels = vec![(self.fuel.clone(), d * 0.005)];
+        let cruise = (bunker - d * 0.005).max(0.0);
+        match self.cruise_plant(d, lwl, leff, cs, ws) {
+            Some(plant) if plant.fuel != self.fuel => fuels.push((plant.fuel.clone(), cruise)),
+            _ => fuels[0].1 += cruise,
+        }
+
+        fuels
+    }
+
+    // economy {{{3
+    /// Distance run on a ton of fuel relative to a coal fired 1910
+    /// reciprocating engine.
+    ///
+    fn economy(&self) -> f64 {
+        (1.0 + 0.4 * (1.0 - self.pct_coal)) * self.boiler.bunker_factor(self.year)
+    }
+
+    // cruise_plant {{{3
+    /// Most economical plant that can drive the ship alone at cruising
+    /// speed, if it is more economical than the main plant.
+    ///
+    pub fn cruise_plant(&self, d: f64, lwl: f64, leff: f64, cs: f64, ws: f64) -> Option<&Plant> {
+        let hp = self.hp_cruise(d, lwl, leff, cs, ws);
+
+        self.plants.iter()
+            .filter(|p| p.role.alone() && p.hp >= hp && p.economy() > self.economy())
+            .max_by(|a, b| a.economy().total_cmp(&b.economy()))
+    }
+
     // bunker_max {{{3
     /// Bunkerage weight at maximum displacement.
     ///
@@ -188,6 +270,100 @@ impl Engine { // {{{2
         self.bunker(d, lwl, leff, cs, ws) * 1.8
     }
 
+    // range_at {{{3
+    /// Range at a given speed when carrying a given bunkerage weight. `hp`
+    /// is the horsepower required to achieve that speed.
+    ///
+    /// This is the inverse of bunker(): carrying bunker() at vcruise gives
+    /// the design range.
+    ///
+    pub fn range_at(&self, v: f64, hp: f64, bunker: f64, d: f64) -> f64 {
+        let fuel = bunker - d * 0.005;
+
+        if v <= 0.0 || hp <= 0.0 || fuel <= 0.0 { return 0.0; } // catch divide by zero
+
+        fuel * self.economy() * 1.8 / hp * Self::RANGE * v * 0.1
+    }
+
+    // modes {{{3
+    /// Speed and range of the ship in each way its plants can be run: the
+    /// main plant alone, each plant that can run alone and all plants
+    /// together at full power.
+    ///
+    /// Range is at cruising speed, or at top speed if that is lower, on the
+    /// fuels the plants running burn.
+    ///
+    pub fn modes(&self, d: f64, lwl: f64, leff: f64, cs: f64, ws: f64) -> Vec<Mode> {
+        let fuels = self.bunker_fuels(d, lwl, leff, cs, ws);
+        let hp_main = self.hp_main(d, lwl, leff, cs, ws);
+
+        // Each running plant is (horsepower, economy, fuel, horsepower type)
+        let mode = |label: String, running: Vec<(f64, f64, FuelType, String)>| {
+            let hp = running.iter().fold(0.0, |hp, r| hp + r.0);
+
+            // Plants rated in different horsepower are summed as plain hp
+            let hp_type = match running.first() {
+                Some(r) if running.iter().all(|o| o.3 == r.3) => r.3.clone(),
+                _ => "hp".into(),
+            };
+            let speed = self.speed_at(hp, d, lwl, leff, cs, ws).min(self.vmax);
+            let v = self.vcruise.min(speed);
+
+            let economy = if hp > 0.0 {
+                running.iter().fold(0.0, |e, r| e + r.0 / hp * r.1)
+            } else {
+                0.0
+            };
+            let fuel = fuels.iter()
+                .filter(|(f, _)| running.iter().any(|r| r.2.intersects(f.clone())))
+                .fold(0.0, |fuel, (_, w)| fuel + w);
+
+            let hp_v = self.hp(v, d, lwl, leff, cs, ws);
+            let range = if v > 0.0 && hp_v > 0.0 {
+                fuel * economy * 1.8 / hp_v * Self::RANGE * v * 0.1
+            } else {
+                0.0
+            };
+
+            Mode { label, hp, hp_type, speed, range }
+        };
+
+        let main = (hp_main, self.economy(), self.fuel.clone(), self.boiler.hp_type());
+
+        let mut modes = vec![mode("Main plant".into(), vec![main.clone()])];
+        for p in self.plants.iter().filter(|p| p.role.alone()) {
+            modes.push(mode(format!("{} plant", p.role), vec![(p.hp, p.economy(), p.fuel.clone(), p.hp_type())]));
+        }
+
+        let mut all = vec![main];
+        all.extend(self.plants.iter()
+            .filter(|p| p.role.at_full_power())
+            .map(|p| (p.hp, p.economy(), p.fuel.clone(), p.hp_type()))
+        );
+        modes.push(mode("All plants".into(), all));
+
+        modes
+    }
+
+    // curves {{{3
+    /// Speed-power and range-speed curves at each of the given speeds.
+    /// Range is given for full bunkerage, ie. bunker_max().
+    ///
+    pub fn curves(&self, speeds: &[f64], d: f64, lwl: f64, leff: f64, cs: f64, ws: f64) -> Vec<SpeedPoint> {
+        let bunker = self.bunker_max(d, lwl, leff, cs, ws);
+
+        speeds.iter().map(|&v| {
+            let hp = self.hp(v, d, lwl, leff, cs, ws);
+
+            SpeedPoint {
+                speed: v,
+                hp,
+                rf:    Self::rf(v, ws),
+                rw:    Self::rw(v, d, lwl, cs),
+                range: self.range_at(v, hp, bunker, d),
+            }
+        }).collect()
+    }
 
     // num_engines {{{3
     /// Number of steam engines.
@@ -209,9 +385,10 @@ impl Engine { // {{{2
             };
 
         (
-            self.hp_max(d, lwl, leff, cs, ws) /
+            self.hp_main(d, lwl, leff, cs, ws) /
             (factor /self.num_engines() as f64 * (1.1 - self.pct_coal / 10.0))
-        ) / early
+        ) / early +
+            self.plants.iter().fold(0.0, |wgt, p| wgt + p.d_engine())
     }
 
 }
@@ -537,6 +714,76 @@ mod engine {
         bunker_max: (40.86, 1000, 0.0, 10.0),
     }
 
+    // Test range_at {{{3
+    macro_rules! test_range_at {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (range, pct_coal, boiler, year) = $value;
+                    let mut eng = Engine::default();
+                    eng.range = range;
+                    eng.pct_coal = pct_coal;
+                    eng.vcruise = 10.0;
+                    eng.vmax = 20.0;
+
+                    eng.boiler = boiler;
+                    eng.year = year;
+                    let lwl = 500.0; let leff = 500.0;
+                    let cs = 0.2563; let ws = 12000.0; let d = 1000.0;
+
+                    let hp = eng.hp_cruise(d, lwl, leff, cs, ws);
+                    let bunker = eng.bunker(d, lwl, leff, cs, ws);
+
+                    assert_eq!(range as f64, to_place(eng.range_at(eng.vcruise, hp, bunker, d), 2));
+                }
+            )*
+        }
+    }
+    test_range_at! {
+        // name:               (range, pct_coal, boiler, year)
+        range_at_oil_turbine:  (5000, 0.0, BoilerType::Turbine, 1920),
+        range_at_coal_turbine: (5000, 1.0, BoilerType::Turbine, 1920),
+        range_at_mixed_simple: (3000, 0.5, BoilerType::Simple, 1905),
+    }
+
+    // Test curves {{{3
+    macro_rules! test_curves {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (speeds, empty) = $value;
+                    let mut eng = Engine::default();
+                    eng.range = 5000;
+                    eng.vcruise = 10.0;
+                    eng.vmax = 20.0;
+
+                    eng.boiler = BoilerType::Turbine;
+                    eng.year = 1920;
+                    let lwl = 500.0; let leff = 500.0;
+                    let cs = 0.2563; let ws = 12000.0; let d = 1000.0;
+
+                    let speeds: &[f64] = speeds;
+                    let curves = eng.curves(speeds, d, lwl, leff, cs, ws);
+
+                    assert_eq!(speeds.len(), curves.len());
+                    for (v, p) in speeds.iter().zip(curves.iter()) {
+                        assert_eq!(*v, p.speed);
+                        assert_eq!(to_place(eng.hp(*v, d, lwl, leff, cs, ws), 2), to_place(p.hp, 2));
+                        assert_eq!(empty, p.range == 0.0);
+                    }
+                }
+            )*
+        }
+    }
+    test_curves! {
+        // name:        (speeds, empty range)
+        curves_speeds:  (&[5.0, 10.0, 20.0], false),
+        curves_stopped: (&[0.0], true),
+        curves_none:    (&[], false),
+    }
+
     // Test d_engine {{{3
     macro_rules! test_d_engine {
         ($($name:ident: $value:expr,)*) => {
@@ -567,6 +814,309 @@ mod engine {
     }
 }
 
+// PlantRole {{{1
+/// How a plant is run together with the main plant.
+///
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum PlantRole {
+    /// Runs alone at cruising speed and is disconnected at full power.
+    #[default]
+    Cruise,
+    /// Runs alone at cruising speed and with the main plant at full power.
+    Combined,
+    /// Runs only with the main plant at full power.
+    Boost,
+}
+
+impl PlantRole { // {{{2
+    // alone {{{3
+    /// Can the plant drive the ship without the main plant?
+    ///
+    pub fn alone(&self) -> bool {
+        matches!(self, Self::Cruise | Self::Combined)
+    }
+
+    // at_full_power {{{3
+    /// Does the plant run with the main plant at full power?
+    ///
+    pub fn at_full_power(&self) -> bool {
+        matches!(self, Self::Combined | Self::Boost)
+    }
+}
+
+impl fmt::Display for PlantRole { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Cruise   => "Cruise",
+                Self::Combined => "Combined",
+                Self::Boost    => "Boost",
+            }
+        )
+    }
+}
+
+// Plant {{{1
+/// A set of machinery alongside the ship's main plant, such as cruising
+/// diesels or boost turbines.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Plant {
+    /// Year plant built.
+    pub year: u32,
+
+    /// Type of fuel.
+    pub fuel: FuelType,
+    /// Type of steam boilers.
+    pub boiler: BoilerType,
+    /// Type of drive.
+    pub drive: DriveType,
+
+    /// Rated horsepower.
+    pub hp: f64,
+    /// How the plant is run with the main plant.
+    pub role: PlantRole,
+}
+
+impl Plant { // {{{2
+    /// Economy of internal combustion motors relative to steam of the
+    /// same year.
+    const MOTOR_ECONOMY: f64 = 1.6;
+
+    // pct_coal {{{3
+    /// Fraction of the plant's fuel that is coal.
+    ///
+    fn pct_coal(&self) -> f64 {
+        if self.fuel == FuelType::Coal { 1.0 } else { 0.0 }
+    }
+
+    // economy {{{3
+    /// Distance run on a ton of fuel relative to a coal fired 1910
+    /// reciprocating engine.
+    ///
+    pub fn economy(&self) -> f64 {
+        (1.0 + 0.4 * (1.0 - self.pct_coal())) * self.boiler.bunker_factor(self.year) *
+            if self.fuel.is_steam() { 1.0 } else { Self::MOTOR_ECONOMY }
+    }
+
+    // hp_type {{{3
+    /// Return the string for the type of horsepower the plant is rated in:
+    /// brake horsepower for internal combustion motors, otherwise that of
+    /// its steam engines.
+    ///
+    pub fn hp_type(&self) -> String {
+        if self.fuel.is_steam() { self.boiler.hp_type() } else { "bhp".into() }
+    }
+
+    // d_engine {{{3
+    /// Displacement of the plant, as Engine::d_engine().
+    ///
+    pub fn d_engine(&self) -> f64 {
+        let factor = self.boiler.d_engine_factor(self.year, self.fuel.clone());
+        if factor <= 0.0 { return 0.0; } // Catch divide by zero
+
+        let early =
+            if self.year <= 1889 {
+                1.0 + (1890 - self.year) as f64 / 100.0
+            } else {
+                1.0
+            };
+
+        self.hp / (factor / self.boiler.num_engines().max(1) as f64 * (1.1 - self.pct_coal() / 10.0)) / early
+    }
+}
+
+// Testing Plant {{{2
+#[cfg(test)]
+mod plant {
+    use super::*;
+    use crate::test_support::*;
+
+    fn get_engine(role: PlantRole, fuel: FuelType) -> Engine {
+        let mut eng = Engine {
+            year: 1930,
+            fuel: FuelType::Oil,
+            boiler: BoilerType::Turbine,
+            drive: DriveType::Geared,
+            vmax: 30.0,
+            vcruise: 15.0,
+            range: 8000,
+            ..Default::default()
+        };
+        eng.plants.push(Plant {
+            year: 1930,
+            boiler: if fuel.is_steam() { BoilerType::Turbine } else { BoilerType::empty() },
+            fuel,
+            drive: DriveType::Direct,
+            hp: 20000.0,
+            role,
+        });
+
+        eng
+    }
+
+    const HULL: (f64, f64, f64, f64, f64) = (5000.0, 500.0, 500.0, 0.2576602375, 30050.0);
+
+    // Test hp_main {{{3
+    macro_rules! test_hp_main {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (d, lwl, leff, cs, ws) = HULL;
+                    let (others, role) = $value;
+
+                    let eng = get_engine(role, FuelType::Diesel);
+                    let hp = eng.hp_max(d, lwl, leff, cs, ws);
+
+                    assert_eq!(to_place(hp - others, 2), to_place(eng.hp_main(d, lwl, leff, cs, ws), 2));
+                }
+            )*
+        }
+    }
+    test_hp_main! {
+        // name:              (hp of other plants at full power, role)
+        hp_main_cruise:       (0.0, PlantRole::Cruise),
+        hp_main_combined:     (20000.0, PlantRole::Combined),
+        hp_main_boost:        (20000.0, PlantRole::Boost),
+    }
+
+    // Test bunker {{{3
+    macro_rules! test_bunker {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (d, lwl, leff, cs, ws) = HULL;
+                    let (cruises, fuels, role, fuel, hp) = $value;
+
+                    let mut eng = get_engine(role, fuel);
+                    eng.plants[0].hp = hp;
+
+                    let mut main = eng.clone();
+                    main.plants.clear();
+
+                    let bunker = eng.bunker(d, lwl, leff, cs, ws);
+                    assert_eq!(cruises, eng.cruise_plant(d, lwl, leff, cs, ws).is_some());
+                    assert_eq!(cruises, bunker < main.bunker(d, lwl, leff, cs, ws));
+
+                    // Bunkerage rolls up across the fuels
+                    let split = eng.bunker_fuels(d, lwl, leff, cs, ws);
+                    assert_eq!(fuels, split.len());
+                    assert_eq!(to_place(bunker, 6), to_place(split.iter().fold(0.0, |b, f| b + f.1), 6));
+                }
+            )*
+        }
+    }
+    test_bunker! {
+        // name:             (cruises, fuels, role, fuel, hp)
+        bunker_diesel:       (true, 2, PlantRole::Cruise, FuelType::Diesel, 20000.0),
+        bunker_combined:     (true, 2, PlantRole::Combined, FuelType::Diesel, 20000.0),
+        bunker_too_small:    (false, 1, PlantRole::Cruise, FuelType::Diesel, 500.0),
+        bunker_boost:        (false, 1, PlantRole::Boost, FuelType::Diesel, 20000.0),
+        bunker_no_better:    (false, 1, PlantRole::Cruise, FuelType::Oil, 20000.0),
+    }
+
+    // Test modes {{{3
+    #[test]
+    fn modes() {
+        let (d, lwl, leff, cs, ws) = HULL;
+
+        let eng = get_engine(PlantRole::Combined, FuelType::Diesel);
+        let modes = eng.modes(d, lwl, leff, cs, ws);
+
+        let labels: Vec<&str> = modes.iter().map(|m| m.label.as_str()).collect();
+        assert_eq!(vec!["Main plant", "Combined plant", "All plants"], labels);
+
+        // Turbines and diesels together are rated in plain horsepower
+        let hp_types: Vec<&str> = modes.iter().map(|m| m.hp_type.as_str()).collect();
+        assert_eq!(vec!["shp", "bhp", "hp"], hp_types);
+
+        // Each plant alone is slower than both together at full power
+        assert_eq!(30.0, to_place(modes[2].speed, 6));
+        assert!(modes[0].speed < 30.0 && modes[1].speed < modes[0].speed);
+        assert_eq!(to_place(eng.hp(modes[1].speed, d, lwl, leff, cs, ws), 0), 20000.0);
+
+        // The diesels cruise further than the turbines
+        assert!(modes[1].range > modes[0].range);
+        assert_eq!(eng.range as f64, to_place(modes[1].range, 0));
+    }
+
+    // Test d_engine {{{3
+    #[test]
+    fn d_engine() {
+        let (d, lwl, leff, cs, ws) = HULL;
+
+        // A boost plant takes power, and weight, off the main plant
+        let eng = get_engine(PlantRole::Boost, FuelType::Oil);
+        let mut main = eng.clone();
+        main.plants.clear();
+
+        let plant = eng.plants[0].d_engine();
+        assert!(plant > 0.0);
+        assert!(eng.d_engine(d, lwl, leff, cs, ws) - plant < main.d_engine(d, lwl, leff, cs, ws));
+
+        assert_eq!(0.0, Plant { year: 1930, ..Default::default() }.d_engine());
+    }
+
+    // Test hp_type {{{3
+    macro_rules! test_hp_type {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, fuel, boiler) = $value;
+
+                    let plant = Plant { year: 1930, fuel, boiler, ..Default::default() };
+                    assert_eq!(expected, plant.hp_type());
+                }
+            )*
+        }
+    }
+    test_hp_type! {
+        // name:              (hp type, fuel, boiler)
+        hp_type_diesel:       ("bhp", FuelType::Diesel, BoilerType::empty()),
+        hp_type_gasoline:     ("bhp", FuelType::Gasoline, BoilerType::empty()),
+        hp_type_turbine:      ("shp", FuelType::Oil, BoilerType::Turbine),
+        hp_type_complex:      ("ihp", FuelType::Coal, BoilerType::Complex),
+    }
+}
+
+// Mode {{{1
+/// Speed and range of the ship with some of its plants running.
+///
+#[derive(Clone, Debug, Default)]
+pub struct Mode {
+    /// Plants running.
+    pub label: String,
+    /// Horsepower of the plants running.
+    pub hp: f64,
+    /// Type of horsepower, or plain hp if the plants running are rated
+    /// differently.
+    pub hp_type: String,
+    /// Top speed (kts).
+    pub speed: f64,
+    /// Range at cruising speed or top speed if lower (nm).
+    pub range: f64,
+}
+
+// SpeedPoint {{{1
+/// Engine and hull performance at one speed.
+///
+#[derive(Clone, Debug, Default)]
+pub struct SpeedPoint {
+    /// Speed (kts).
+    pub speed: f64,
+    /// Shaft horsepower required.
+    pub hp: f64,
+    /// Friction resistance.
+    pub rf: f64,
+    /// Wave resistance.
+    pub rw: f64,
+    /// Range at full bunkerage (nm).
+    pub range: f64,
+}
+
 // FuelType {{{1
 //
 bitflags! {
@@ -622,6 +1172,16 @@ impl FuelType { // {{{2
     pub fn is_steam(&self) -> bool {
         self.contains(Self::Coal) || self.contains(Self::Oil)
     }
+
+    // name {{{3
+    /// Short lower case name of the fuels, such as "coal and oil".
+    ///
+    pub fn name(&self) -> String {
+        self.iter_names()
+            .map(|(name, _)| name.to_lowercase())
+            .collect::<Vec<_>>()
+            .join(" and ")
+    }
 }
 
 // Testing FuelType {{{2
@@ -652,6 +1212,14 @@ mod fuel_type {
         is_steam_battery:  (false, FuelType::Battery),
         is_steam_multiple: (true, FuelType::Coal | FuelType::Diesel),
     }
+
+    // Test name {{{3
+    #[test]
+    fn name() {
+        assert_eq!("oil", FuelType::Oil.name());
+        assert_eq!("coal and oil", (FuelType::Coal | FuelType::Oil).name());
+        assert_eq!("", FuelType::empty().name());
+    }
 }
 
 // BoilerType {{{1
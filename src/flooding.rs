@@ -0,0 +1,655 @@
+use crate::{Ship, LoadType};
+use crate::Hull;
+
+use format_num::format_num;
+
+use std::fmt;
+use std::str::FromStr;
+
+// Location {{{1
+/// Where a hit strikes the ship.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Location {
+    /// Main belt, over the vital spaces.
+    Belt,
+    /// Unarmoured or lightly armoured end forward of the vital spaces.
+    Bow,
+    /// Unarmoured or lightly armoured end aft of the vital spaces.
+    Stern,
+    /// Below the belt, alongside the vital spaces.
+    Underwater,
+}
+
+impl FromStr for Location { // {{{2
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "belt"                => Ok(Self::Belt),
+            "bow"                 => Ok(Self::Bow),
+            "stern"               => Ok(Self::Stern),
+            "underwater" | "uw"   => Ok(Self::Underwater),
+            _ => Err(format!("Unknown location '{}': use one of belt, bow, stern, underwater", s)),
+        }
+    }
+}
+
+impl fmt::Display for Location { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Belt       => "belt",
+                Self::Bow        => "bow",
+                Self::Stern      => "stern",
+                Self::Underwater => "underwater",
+            }
+        )
+    }
+}
+
+// Weapon {{{1
+/// What strikes the ship.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Weapon {
+    /// Shell of a given diameter (in).
+    Shell(f64),
+    /// Torpedo or mine with a given charge (lbs).
+    Torpedo(f64),
+}
+
+impl FromStr for Weapon { // {{{2
+    type Err = String;
+
+    /// A plain number is the diameter of a shell and a number ending in
+    /// "lb" or "lbs" is the charge of a torpedo.
+    ///
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.to_lowercase();
+        let err = |_| format!("Unknown weapon '{}': use a shell diameter such as 14 or a torpedo charge such as 500lb", s);
+
+        match s.strip_suffix("lbs").or(s.strip_suffix("lb")) {
+            Some(charge) => Ok(Self::Torpedo(charge.parse().map_err(err)?)),
+            None         => Ok(Self::Shell(s.parse().map_err(err)?)),
+        }
+    }
+}
+
+impl fmt::Display for Weapon { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Shell(diam)     => write!(f, "{:.1}\" shell", diam),
+            Self::Torpedo(charge) => write!(f, "{} lbs torpedo", format_num!(",.0", *charge)),
+        }
+    }
+}
+
+// Strike {{{1
+/// A single hit on the ship.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Strike {
+    /// Where the hit lands.
+    pub location: Location,
+    /// What hits the ship.
+    pub weapon: Weapon,
+    /// True if the hit is on the starboard side.
+    pub starboard: bool,
+}
+
+impl FromStr for Strike { // {{{2
+    type Err = String;
+
+    /// Parse LOCATION:WEAPON[:SIDE], such as belt:14 or underwater:500lb:port.
+    /// Hits are to starboard unless the side is given.
+    ///
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(':').collect();
+
+        let starboard = match parts.get(2).map(|side| side.to_lowercase()).as_deref() {
+            None | Some("starboard") | Some("stbd") => true,
+            Some("port") => false,
+            Some(side) => return Err(format!("Unknown side '{}': use port or starboard", side)),
+        };
+
+        match parts.as_slice() {
+            [location, weapon] | [location, weapon, _] => Ok(Self {
+                location: location.parse()?,
+                weapon: weapon.parse()?,
+                starboard,
+            }),
+            _ => Err(format!("Unknown hit '{}': use LOCATION:WEAPON[:SIDE]", s)),
+        }
+    }
+}
+
+impl fmt::Display for Strike { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {}",
+            self.weapon,
+            match self.location {
+                Location::Underwater => "underwater",
+                Location::Belt       => "on the belt",
+                Location::Bow        => "in the bow",
+                Location::Stern      => "in the stern",
+            },
+            if self.starboard { "to starboard" } else { "to port" }
+        )
+    }
+}
+
+// Testing Strike {{{2
+#[cfg(test)]
+mod strike {
+    use super::*;
+
+    // Test from_str {{{3
+    macro_rules! test_from_str {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, s) = $value;
+
+                    assert_eq!(expected, s.parse::<Strike>());
+                }
+            )*
+        }
+    }
+    test_from_str! {
+        // name:            (strike, string)
+        from_str_shell:     (Ok(Strike { location: Location::Belt, weapon: Weapon::Shell(14.0), starboard: true }), "belt:14"),
+        from_str_torpedo:   (Ok(Strike { location: Location::Underwater, weapon: Weapon::Torpedo(500.0), starboard: false }), "UW:500lbs:Port"),
+        from_str_bow:       (Ok(Strike { location: Location::Bow, weapon: Weapon::Shell(6.0), starboard: true }), "bow:6:stbd"),
+        from_str_no_weapon: (Err("Unknown hit 'belt': use LOCATION:WEAPON[:SIDE]".to_string()), "belt"),
+        from_str_location:  (Err("Unknown location 'deck': use one of belt, bow, stern, underwater".to_string()), "deck:14"),
+        from_str_weapon:    (Err("Unknown weapon 'big': use a shell diameter such as 14 or a torpedo charge such as 500lb".to_string()), "belt:big"),
+        from_str_side:      (Err("Unknown side 'aft': use port or starboard".to_string()), "belt:14:aft"),
+    }
+}
+
+// Space {{{1
+/// Part of the ship a hit can flood.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Space {
+    /// Forward of the vital spaces.
+    Bow,
+    /// Aft of the vital spaces.
+    Stern,
+    /// Between the side of the ship and the torpedo bulkhead, to starboard
+    /// if true.
+    Side(bool),
+    /// Machinery spaces and magazines.
+    Vitals,
+}
+
+impl fmt::Display for Space { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Bow         => "bow",
+                Self::Stern       => "stern",
+                Self::Side(true)  => "starboard side",
+                Self::Side(false) => "port side",
+                Self::Vitals      => "vital spaces",
+            }
+        )
+    }
+}
+
+// Compartment {{{1
+/// A watertight compartment between two transverse bulkheads.
+///
+#[derive(Clone, Debug, PartialEq)]
+pub struct Compartment {
+    /// Part of the ship the compartment is in.
+    pub space: Space,
+    /// Distance of the centre forward of midships (ft).
+    pub x: f64,
+    /// Distance of the centre to starboard of the centreline (ft).
+    pub y: f64,
+    /// Length (ft).
+    pub len: f64,
+    /// Width (ft).
+    pub width: f64,
+    /// Fraction of the space that water can fill, including the fining of
+    /// the hull at the ends.
+    pub perm: f64,
+}
+
+impl Compartment { // {{{2
+    // water {{{3
+    /// Weight of water (t) in the compartment when flooded to a given
+    /// height above the keel.
+    ///
+    pub fn water(&self, hgt: f64) -> f64 {
+        self.perm * self.len * self.width * hgt.max(0.0) / Hull::FT3_PER_TON_SEA
+    }
+}
+
+// FloodResult {{{1
+/// State of a ship after flooding.
+///
+#[derive(Clone, Debug, Default)]
+pub struct FloodResult {
+    /// Compartments flooded.
+    pub flooded: Vec<Compartment>,
+    /// Weight of water taken on (t).
+    pub water: f64,
+    /// Mean draft (ft).
+    pub t: f64,
+    /// Trim (ft), positive when down by the bow.
+    pub trim: f64,
+    /// Heel (degrees), positive to starboard.
+    pub heel: f64,
+    /// Buoyancy left between the waterline and the deck (t).
+    pub reserve: f64,
+    /// The deck goes under or no buoyancy is left.
+    pub sinks: bool,
+    /// The heeling moment of the water is more than the ship can right.
+    pub capsizes: bool,
+}
+
+// Flooding {{{1
+/// Hits on a ship and the compartments they flood.
+///
+#[derive(Clone, Debug)]
+pub struct Flooding<'a> {
+    /// Ship that is hit.
+    pub ship: &'a Ship,
+    /// Hits in the order they land.
+    pub strikes: Vec<Strike>,
+}
+
+impl<'a> Flooding<'a> { // {{{2
+    /// Spacing of transverse bulkheads as a fraction of waterline length.
+    ///
+    const SPACING: f64 = 0.05;
+
+    /// Width of the side spaces of a ship without torpedo bulkheads as a
+    /// fraction of beam.
+    ///
+    const WING: f64 = 0.15;
+
+    /// Permeability of the side spaces and ends.
+    ///
+    const PERM_WING: f64 = 0.95;
+
+    /// Permeability of the machinery spaces and magazines.
+    ///
+    const PERM_VITAL: f64 = 0.85;
+
+    /// Number of times the draft and trim are worked out again.
+    ///
+    const ITERATIONS: u32 = 20;
+
+    // new {{{3
+    /// Hits on a ship.
+    ///
+    pub fn new(ship: &'a Ship, strikes: Vec<Strike>) -> Self {
+        Self { ship, strikes }
+    }
+
+    // depth {{{3
+    /// Height of the deck above the keel (ft).
+    ///
+    fn depth(&self) -> f64 {
+        self.ship.hull.t + self.ship.hull.freeboard_dist()
+    }
+
+    // vital_len {{{3
+    /// Length of the vital spaces: the length of the main belt, or the
+    /// length belt_coverage() expects if there is none.
+    ///
+    fn vital_len(&self) -> f64 {
+        let (lwl, belt) = (self.ship.hull.lwl(), self.ship.armor.main.len);
+
+        if belt > 0.0 { belt.min(lwl) } else { lwl * 0.65 }
+    }
+
+    // wing {{{3
+    /// Width of the side spaces: out to the torpedo bulkheads if there are
+    /// any.
+    ///
+    fn wing(&self) -> f64 {
+        let (armor, bb) = (&self.ship.armor, self.ship.hull.bb);
+
+        if armor.bulkhead.thick > 0.0 && armor.bh_beam > 0.0 && armor.bh_beam < bb {
+            (bb - armor.bh_beam) / 2.0
+        } else {
+            bb * Self::WING
+        }
+    }
+
+    // compartment {{{3
+    /// The nth compartment of a part of the ship to flood, if there is one.
+    ///
+    /// Compartments at the ends flood from the stem or stern inwards and
+    /// those in the vital spaces from midships outwards, alternately
+    /// forward and aft.
+    ///
+    pub fn compartment(&self, space: Space, n: usize) -> Option<Compartment> {
+        let hull = &self.ship.hull;
+        let (lwl, bb) = (hull.lwl(), hull.bb);
+        let (vital, wing) = (self.vital_len(), self.wing());
+        let spacing = lwl * Self::SPACING;
+
+        let (x, y, len, width, perm) = match space {
+            Space::Bow | Space::Stern => {
+                let end = (lwl - vital) / 2.0;
+                let from = n as f64 * spacing;
+                if from >= end { return None; }
+
+                let len = spacing.min(end - from);
+                let x = lwl / 2.0 - from - len / 2.0;
+
+                // The ends are finer than the rest of the hull
+                (if space == Space::Bow { x } else { -x }, 0.0, len, bb, Self::PERM_WING * hull.cb())
+            },
+            Space::Side(_) | Space::Vitals => {
+                // Compartments 0, 1, 2, 3 ... centred at 0, +1, -1, +2 ... spacings
+                let step = n.div_ceil(2) as f64 * if n % 2 == 1 { 1.0 } else { -1.0 };
+                let x = step * spacing;
+                if x.abs() + spacing / 2.0 > vital / 2.0 && n > 0 { return None; }
+
+                let len = spacing.min(vital);
+                match space {
+                    Space::Side(starboard) =>
+                        (x, (bb - wing) / 2.0 * if starboard { 1.0 } else { -1.0 }, len, wing, Self::PERM_WING),
+                    _ => (x, 0.0, len, bb - 2.0 * wing, Self::PERM_VITAL),
+                }
+            },
+        };
+
+        Some(Compartment { space, x, y, len, width, perm })
+    }
+
+    // floods {{{3
+    /// Parts of the ship flooded by a hit.
+    ///
+    /// A shell gets through armour thinner than its own diameter. A torpedo
+    /// always floods the side space it hits and floods the vital spaces too
+    /// if its charge is more than the torpedo protection can hold.
+    ///
+    pub fn floods(&self, strike: &Strike) -> Vec<Space> {
+        let (hull, armor) = (&self.ship.hull, &self.ship.armor);
+        let side = Space::Side(strike.starboard);
+
+        match (strike.location, strike.weapon) {
+            (Location::Bow, Weapon::Shell(diam)) if diam <= armor.end.thick => vec![],
+            (Location::Stern, Weapon::Shell(diam)) if diam <= armor.end.thick => vec![],
+            (Location::Bow, _) => vec![Space::Bow],
+            (Location::Stern, _) => vec![Space::Stern],
+
+            (Location::Belt, Weapon::Shell(diam)) if diam <= armor.main.thick => vec![],
+            (Location::Belt, Weapon::Shell(_)) => vec![side],
+            (Location::Underwater, Weapon::Shell(_)) => vec![side],
+
+            (_, Weapon::Torpedo(charge)) =>
+                if charge > armor.tds_charge(hull.b, hull.bb) { vec![side, Space::Vitals] } else { vec![side] },
+        }
+    }
+
+    // flooded {{{3
+    /// Compartments flooded by each hit, in order. Each hit on a part of
+    /// the ship floods the next compartment of that part.
+    ///
+    pub fn flooded(&self) -> Vec<Vec<Compartment>> {
+        let mut counts: Vec<(Space, usize)> = Vec::new();
+
+        self.strikes.iter().map(|strike| {
+            self.floods(strike).into_iter().filter_map(|space| {
+                let n = match counts.iter_mut().find(|(s, _)| *s == space) {
+                    Some((_, n)) => { *n += 1; *n - 1 },
+                    None => { counts.push((space, 1)); 0 },
+                };
+
+                self.compartment(space, n)
+            }).collect()
+        }).collect()
+    }
+
+    // result {{{3
+    /// Draft, trim, heel and reserve buoyancy after all hits.
+    ///
+    /// Water is added as weight. Each flooded compartment fills up to the
+    /// waterline at its centre, up to the deck, and the draft and trim are
+    /// worked out again until they settle. Heel is where the righting arm
+    /// at normal load matches the heeling arm of the water.
+    ///
+    pub fn result(&self) -> FloodResult {
+        let ship = self.ship;
+        let (d, t, lwl) = (ship.hull.d(), ship.hull.t, ship.hull.lwl());
+        let depth = self.depth();
+
+        let flooded: Vec<Compartment> = self.flooded().into_iter().flatten().collect();
+
+        let (mut water, mut t_new, mut trim) = (0.0, t, 0.0);
+        for _ in 0..Self::ITERATIONS {
+            let hgt = |c: &Compartment| (t_new + trim * c.x / lwl).min(depth);
+
+            water = flooded.iter().fold(0.0, |sum, c| sum + c.water(hgt(c)));
+            let moment = flooded.iter().fold(0.0, |sum, c| sum + c.water(hgt(c)) * c.x);
+
+            t_new = ship.hull.t_calc(d + water);
+            let bml = ship.hull.bml_calc(d + water, t_new);
+            trim = if bml > 0.0 { moment * lwl / ((d + water) * bml) } else { 0.0 };
+        }
+
+        // Heel to where the righting arm matches the heeling arm
+        let hgt = |c: &Compartment| (t_new + trim * c.x / lwl).min(depth);
+        let arm = flooded.iter().fold(0.0, |sum, c| sum + c.water(hgt(c)) * c.y) / (d + water);
+
+        let curve = ship.gz_curve(&LoadType::Normal);
+        let heel = curve.points.windows(2)
+            .find(|p| p[0].gz <= arm.abs() && p[1].gz >= arm.abs())
+            .map(|p| p[0].heel + (p[1].heel - p[0].heel) * (arm.abs() - p[0].gz) / (p[1].gz - p[0].gz));
+
+        let reserve = ship.hull.wp() * (depth - t) / Hull::FT3_PER_TON_SEA - water;
+
+        FloodResult {
+            flooded,
+            water,
+            t: t_new,
+            trim,
+            heel: heel.unwrap_or(90.0) * arm.signum(),
+            reserve,
+            sinks: reserve <= 0.0 || t_new + trim.abs() / 2.0 >= depth,
+            capsizes: heel.is_none(),
+        }
+    }
+
+    // report {{{3
+    /// Describe each hit, what it floods and the state of the ship after
+    /// all of them.
+    ///
+    pub fn report(&self) -> String {
+        let mut r: Vec<String> = Vec::new();
+        let ship = self.ship;
+        let result = self.result();
+
+        r.push(format!("{}: {} tons, {:.2} ft draft", ship.name, format_num!(",.0", ship.hull.d()), ship.hull.t));
+        r.push("".into());
+
+        for (strike, flooded) in self.strikes.iter().zip(self.flooded()) {
+            r.push(format!("{}: {}", strike,
+                if self.floods(strike).is_empty() {
+                    "stopped by armour".to_string()
+                } else if flooded.is_empty() {
+                    "no more compartments to flood".to_string()
+                } else {
+                    "floods ".to_string() + &flooded.iter()
+                        .map(|c| c.space.to_string())
+                        .collect::<Vec<String>>()
+                        .join(" and ")
+                }
+            ));
+        }
+        r.push("".into());
+
+        r.push(format!("{} tons of water in {} compartments",
+            format_num!(",.0", result.water), result.flooded.len()));
+        r.push(format!("Draft {:.2} ft ({:+.2} ft), trim {:.2} ft by the {}, heel {:.1} degrees to {}",
+            result.t,
+            result.t - ship.hull.t,
+            result.trim.abs(),
+            if result.trim >= 0.0 { "bow" } else { "stern" },
+            result.heel.abs(),
+            if result.heel >= 0.0 { "starboard" } else { "port" },
+        ));
+        r.push(format!("Reserve buoyancy {} tons", format_num!(",.0", result.reserve.max(0.0))));
+
+        r.push(
+            if result.capsizes   { "Ship capsizes".into() }
+            else if result.sinks { "Ship sinks".into() }
+            else                 { "Ship stays afloat".into() }
+        );
+
+        r.join("\n")
+    }
+}
+
+// Testing Flooding {{{2
+#[cfg(test)]
+mod flood {
+    use super::*;
+    use crate::test_support::{self, get_sized_hull};
+
+    fn get_ship() -> Ship {
+        let mut ship = test_support::get_ship(1920,
+            get_sized_hull(20_000.0, 600.0, 90.0, 28.0), (0, 0.0, 0.0, 0));
+
+        ship.name = "Target".into();
+        ship.hull.fd_fwd = 20.0;
+        ship.engine.vmax = 21.0;
+        ship.engine.vcruise = 10.0;
+
+        ship.armor.main.thick = 12.0;
+        ship.armor.main.len = 400.0;
+        ship.armor.main.hgt = 15.0;
+        ship.armor.end.thick = 4.0;
+        ship.armor.bulkhead.thick = 2.0;
+        ship.armor.bulkhead.len = 400.0;
+        ship.armor.bulkhead.hgt = 30.0;
+        ship.armor.bh_beam = 66.0;
+
+        ship
+    }
+
+    // Test floods {{{3
+    macro_rules! test_floods {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, strike): (Vec<Space>, &str) = $value;
+
+                    let ship = get_ship();
+                    let flooding = Flooding::new(&ship, vec![]);
+
+                    assert_eq!(expected, flooding.floods(&strike.parse().unwrap()));
+                }
+            )*
+        }
+    }
+    test_floods! {
+        // name:                (compartments, strike)
+        floods_belt_stopped:    (vec![], "belt:12"),
+        floods_belt_pierced:    (vec![Space::Side(true)], "belt:14"),
+        floods_belt_port:       (vec![Space::Side(false)], "belt:14:port"),
+        floods_bow_stopped:     (vec![], "bow:4"),
+        floods_bow:             (vec![Space::Bow], "bow:6"),
+        floods_stern:           (vec![Space::Stern], "stern:6"),
+        floods_underwater:      (vec![Space::Side(true)], "underwater:6"),
+        floods_torpedo_held:    (vec![Space::Side(true)], "underwater:500lb"),
+        floods_torpedo_vitals:  (vec![Space::Side(true), Space::Vitals], "belt:1000lb"),
+        floods_torpedo_bow:     (vec![Space::Bow], "bow:1000lb"),
+    }
+
+    // Test compartment {{{3
+    macro_rules! test_compartment {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, space, n) = $value;
+
+                    let ship = get_ship();
+                    let flooding = Flooding::new(&ship, vec![]);
+
+                    assert_eq!(expected, flooding.compartment(space, n).map(|c| (c.x, c.y, c.len)));
+                }
+            )*
+        }
+    }
+    test_compartment! {
+        // name:                 ((x, y, len), space, n)
+        compartment_bow:         (Some((285.0, 0.0, 30.0)), Space::Bow, 0),
+        compartment_bow_last:    (Some((205.0, 0.0, 10.0)), Space::Bow, 3),
+        compartment_bow_none:    (None, Space::Bow, 4),
+        compartment_stern:       (Some((-255.0, 0.0, 30.0)), Space::Stern, 1),
+        compartment_side:        (Some((0.0, 39.0, 30.0)), Space::Side(true), 0),
+        compartment_side_fwd:    (Some((30.0, -39.0, 30.0)), Space::Side(false), 1),
+        compartment_side_aft:    (Some((-30.0, -39.0, 30.0)), Space::Side(false), 2),
+        compartment_vitals_last: (Some((-180.0, 0.0, 30.0)), Space::Vitals, 12),
+        compartment_vitals_none: (None, Space::Vitals, 13),
+    }
+
+    // Test result {{{3
+    macro_rules! test_result {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (strikes, trim, heel, sinks, capsizes): (Vec<&str>, f64, f64, bool, bool) = $value;
+
+                    let ship = get_ship();
+                    let strikes = strikes.iter().map(|s| s.parse().unwrap()).collect();
+                    let result = Flooding::new(&ship, strikes).result();
+
+                    assert_eq!(result.water > 0.0, result.t > ship.hull.t);
+                    assert_eq!((trim, heel), (result.trim.signum(), result.heel.signum()));
+                    assert_eq!((sinks, capsizes), (result.sinks, result.capsizes));
+                }
+            )*
+        }
+    }
+    test_result! {
+        // name:            ([strikes], trim sign, heel sign, sinks, capsizes)
+        result_none:        (vec![], 1.0, 1.0, false, false),
+        result_stopped:     (vec!["belt:11"], 1.0, 1.0, false, false),
+        result_bow:         (vec!["bow:8"], 1.0, 1.0, false, false),
+        result_stern:       (vec!["stern:8"], -1.0, 1.0, false, false),
+        result_port:        (vec!["belt:1000lb:port"], 1.0, -1.0, false, false),
+        result_sinks:       (vec!["bow:8"; 6], 1.0, 1.0, true, false),
+    }
+
+    // Test capsizes {{{3
+    #[test]
+    fn capsizes() {
+        let mut ship = get_ship();
+        let strikes = vec!["underwater:500lb:port".parse().unwrap(); 6];
+
+        let result = Flooding::new(&ship, strikes.clone()).result();
+        assert!(result.heel < 0.0 && !result.capsizes);
+
+        // Weight high in the ship leaves less righting arm
+        ship.wgts.above = 9_000;
+        let result = Flooding::new(&ship, strikes).result();
+        assert!(result.capsizes);
+    }
+
+    // Test report {{{3
+    #[test]
+    fn report() {
+        let ship = get_ship();
+        let strikes = vec!["belt:12".parse().unwrap(), "underwater:1000lb:port".parse().unwrap()];
+        let report = Flooding::new(&ship, strikes).report();
+
+        assert!(report.contains("12.0\" shell on the belt to starboard: stopped by armour"), "{}", report);
+        assert!(report.contains("1,000 lbs torpedo underwater to port: floods port side and vital spaces"), "{}", report);
+        assert!(report.contains("heel"), "{}", report);
+    }
+}
@@ -266,20 +266,28 @@ impl Hull { // {{{2
     /// Waterplane Area Coefficient (Parsons).
     ///
     pub fn cwp(&self) -> f64 {
+        self.cwp_calc(self.cb())
+    }
+
+    // cwp_calc {{{3
+    /// Calculate the Waterplane Area Coefficient for a given Block
+    /// Coefficient.
+    ///
+    pub fn cwp_calc(&self, cb: f64) -> f64 {
         let (a, f) = 
-            if self.boxy || self.cb() >= 0.75 {
+            if self.boxy || cb >= 0.75 {
                 (0.175, 0.875)
             } else {
                 self.stern_type.wp_calc()
             };
         
         let cwp = f64::min(
-            a + f * Hull::cp( f64::max(self.cb(), 0.4) ),
+            a + f * Hull::cp( f64::max(cb, 0.4) ),
             1.0
         );
 
-        cwp - if self.cb() < 0.4 {
-                0.0281 - (self.cb() - 0.3).powf(1.55)
+        cwp - if cb < 0.4 {
+                0.0281 - (cb - 0.3).powf(1.55)
             } else {
                 0.0
             }
@@ -289,7 +297,14 @@ impl Hull { // {{{2
     /// Waterplane Area.
     ///
     pub fn wp(&self) -> f64 {
-        self.cwp() * self.lwl() * self.b
+        self.wp_calc(self.cb())
+    }
+
+    // wp_calc {{{3
+    /// Calculate the Waterplane Area for a given Block Coefficient.
+    ///
+    pub fn wp_calc(&self, cb: f64) -> f64 {
+        self.cwp_calc(cb) * self.lwl() * self.b
     }
 
     // ws {{{3
@@ -417,6 +432,32 @@ impl Hull { // {{{2
        (self.fd() * self.fd_len + self.ad() * self.ad_len()) / (self.fd_len + self.ad_len()) 
     }
 
+    // profile_x {{{3
+    /// Distance of the centre of the side profile forward of midships
+    /// (ft), from the depth of each deck along the length of the hull.
+    ///
+    pub fn profile_x(&self) -> f64 {
+        let decks = [
+            (self.fc_len, self.fc()),
+            (self.fd_len, self.fd()),
+            (self.ad_len(), self.ad()),
+            (self.qd_len, self.qd()),
+        ];
+
+        let (mut start, mut area, mut moment) = (0.0, 0.0, 0.0);
+        for (len, fb) in decks {
+            let a = len * (self.t + fb);
+
+            area   += a;
+            moment += a * (0.5 - start - len / 2.0);
+            start  += len;
+        }
+
+        if area <= 0.0 { return 0.0; } // Catch divide by zero
+
+        moment / area * self.lwl()
+    }
+
     // is_wet_fwd {{{3
     /// Does the ship tend to be wet forward?
     ///
@@ -483,6 +524,53 @@ impl Hull { // {{{2
         self.lwl() / self.bb
     }
 
+    // km {{{3
+    /// Height of the transverse metacentre above the keel at normal
+    /// displacement.
+    ///
+    pub fn km(&self) -> f64 {
+        self.km_calc(self.d(), self.t)
+    }
+
+    // km_calc {{{3
+    /// Calculate the height of the transverse metacentre above the keel for
+    /// a given displacement and draft.
+    ///
+    /// The centre of buoyancy is from Morrish and the inertia of the
+    /// waterplane from Normand.
+    ///
+    pub fn km_calc(&self, d: f64, t: f64) -> f64 {
+        if d <= 0.0 || t <= 0.0 { return 0.0; } // Catch divide by zero.
+
+        let cb = self.cb_calc(d, t);
+        let cwp = self.cwp_calc(cb);
+
+        let kb = t * (5.0 / 6.0 - cb / (3.0 * cwp));
+        let bm = (0.096 + 0.89 * cwp.powf(2.0)) / 12.0 *
+            self.lwl() * self.bb.powf(3.0) / (d * Self::FT3_PER_TON_SEA);
+
+        kb + bm
+    }
+
+    // bml {{{3
+    /// Longitudinal metacentric radius at normal displacement.
+    ///
+    pub fn bml(&self) -> f64 {
+        self.bml_calc(self.d(), self.t)
+    }
+
+    // bml_calc {{{3
+    /// Calculate the longitudinal metacentric radius for a given
+    /// displacement and draft.
+    ///
+    pub fn bml_calc(&self, d: f64, t: f64) -> f64 {
+        if d <= 0.0 || t <= 0.0 { return 0.0; } // Catch divide by zero.
+
+        let cwp = self.cwp_calc(self.cb_calc(d, t));
+
+        0.075 * cwp.powf(2.0) * self.bb * self.lwl().powf(3.0) / (d * Self::FT3_PER_TON_SEA)
+    }
+
 }
 
 // Testing Hull {{{2
@@ -594,6 +682,121 @@ mod hull {
         cb_solid_block:   (1.0, 100.0, Hull::FT3_PER_TON_SEA, 1.0, 1.0),
     }
 
+    // km {{{3
+    macro_rules! test_km {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, d, lwl, bb, t) = $value;
+
+                    let mut hull = Hull::default();
+                    hull.set_d(d);
+                    hull.set_lwl(lwl);
+                    hull.bb = bb;
+                    hull.t = t;
+
+                    assert_eq!(expected, to_place(hull.km(), 2));
+                }
+            )*
+        }
+    }
+    test_km! {
+        // name:      (km, d, lwl, bb, t)
+        km_d_eq_zero: (0.0, 0.0, 800.0, 50.0, 10.0),
+        km_t_eq_zero: (0.0, 8000.0, 800.0, 50.0, 0.0),
+        km_test:      (25.3, 8000.0, 800.0, 50.0, 10.0),
+    }
+
+    // bml {{{3
+    macro_rules! test_bml {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, d, lwl, bb, t) = $value;
+
+                    let mut hull = Hull::default();
+                    hull.set_d(d);
+                    hull.set_lwl(lwl);
+                    hull.bb = bb;
+                    hull.t = t;
+
+                    assert_eq!(expected, to_place(hull.bml(), 1));
+                }
+            )*
+        }
+    }
+    test_bml! {
+        // name:       (bml, d, lwl, bb, t)
+        bml_d_eq_zero: (0.0, 0.0, 800.0, 50.0, 10.0),
+        bml_t_eq_zero: (0.0, 8000.0, 800.0, 50.0, 0.0),
+        bml_test:      (4405.3, 8000.0, 800.0, 50.0, 10.0),
+    }
+
+    // bml_calc {{{3
+    macro_rules! test_bml_calc {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, d, t) = $value;
+
+                    let mut hull = Hull::default();
+                    hull.set_d(8000.0);
+                    hull.set_lwl(800.0);
+                    hull.bb = 50.0;
+                    hull.t = 10.0;
+
+                    assert_eq!(expected, to_place(hull.bml_calc(d, t), 1));
+                }
+            )*
+        }
+    }
+    test_bml_calc! {
+        // name:            (bml, d, t)
+        bml_calc_d_eq_zero: (0.0, 0.0, 10.0),
+        bml_calc_t_eq_zero: (0.0, 8000.0, 0.0),
+        bml_calc_normal:    (4405.3, 8000.0, 10.0),
+        bml_calc_deep:      (3710.8, 10000.0, 12.0),
+        bml_calc_light:     (5427.2, 6000.0, 8.0),
+    }
+
+    // profile_x {{{3
+    macro_rules! test_profile_x {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, fc, qd) = $value;
+
+                    let mut hull = Hull::default();
+                    hull.set_lwl(400.0);
+                    hull.t = 10.0;
+                    hull.fc_len = 0.2;
+                    hull.fd_len = 0.3;
+                    hull.qd_len = 0.0;
+                    (hull.fc_fwd, hull.fc_aft) = (fc, fc);
+                    (hull.fd_fwd, hull.fd_aft) = (20.0, 20.0);
+                    (hull.ad_fwd, hull.ad_aft) = (20.0, 20.0);
+                    if qd > 0.0 {
+                        hull.fd_len = 0.1;
+                        hull.qd_len = 0.2;
+                        (hull.qd_fwd, hull.qd_aft) = (qd, qd);
+                    }
+
+                    assert_eq!(expected, to_place(hull.profile_x(), 2));
+                }
+            )*
+        }
+    }
+    test_profile_x! {
+        // name:              (profile x, forecastle, quarter deck)
+        profile_x_flush:      (0.0, 20.0, 0.0),
+        profile_x_forecastle: (10.0, 30.0, 0.0),
+        profile_x_low_qd:     (21.33, 30.0, 10.0),
+    }
+
     // d {{{3
     macro_rules! test_d {
         ($($name:ident: $value:expr,)*) => {
@@ -651,6 +854,31 @@ mod hull {
         cwp_test_4: (0.59708, false, 0.35),
     }
 
+    // wp_calc {{{3
+    macro_rules! test_wp_calc {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, cb) = $value;
+
+                    let mut hull = Hull::default();
+                    hull.set_lwl(500.0);
+                    hull.b = 50.0;
+                    hull.set_cb(0.5);
+
+                    assert_eq!(expected, to_place(hull.wp_calc(cb), 2));
+                }
+            )*
+        }
+    }
+    test_wp_calc! {
+        // name:            (wp, cb)
+        wp_calc_normal:     (16656.89, 0.5),
+        wp_calc_full:       (20940.18, 0.75),
+        wp_calc_fine:       (14927.07, 0.35),
+    }
+
     // ws {{{3
     macro_rules! test_ws {
         ($($name:ident: $value:expr,)*) => {
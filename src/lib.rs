@@ -5,15 +5,48 @@ mod armor;
 use armor::{Armor, BulkheadType};
 
 mod engine;
-use engine::{Engine, FuelType, BoilerType, DriveType};
+use engine::{Engine, FuelType, BoilerType, DriveType, SpeedPoint};
+pub use engine::{Plant, PlantRole, Mode};
 
 mod weapons;
-use weapons::{Battery, Torpedoes, Mines, ASW};
-use weapons::{MountType, GunDistributionType};
+use weapons::{Battery, SubBattery, Torpedoes, Mines, ASW};
+use weapons::{MountType, GunDistributionType, AABand};
+pub use weapons::ShellType;
 
 mod weights;
 use weights::MiscWgts;
 
+mod rig;
+pub use rig::{Rig, RigType};
+
+mod rules;
+pub use rules::{Treaty, ShipCategory};
+
+mod ballistics;
+pub use ballistics::{Gun, Shot};
+
+mod duel;
+pub use duel::{Duel, Outcome};
+
+mod arcs;
+pub use arcs::{Mount, Bearing};
+
+mod stability;
+pub use stability::{GzCurve, GzPoint};
+use stability::Section;
+
+mod flooding;
+pub use flooding::{Flooding, FloodResult, Compartment, Space, Strike, Location, Weapon};
+
+mod strength;
+pub use strength::{Girder, Strength, StrengthPoint, Wave};
+
+mod submarine;
+pub use submarine::{ShipKind, PressureHull};
+
+mod aviation;
+pub use aviation::Aviation;
+
 mod units;
 use units::Units::*;
 use units::metric;
@@ -25,6 +58,7 @@ use serde::{Serialize, Deserialize};
 use serde_json::Value;
 
 use std::error::Error;
+use std::fmt;
 use std::fs;
 use std::fs::{File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
@@ -48,6 +82,8 @@ struct Version {
 // Testing support {{{1
 #[cfg(test)]
 mod test_support {
+    use crate::{Ship, Hull};
+
     // Round a float to a given number of digits
     //
     // This makes it much easier to test results that
@@ -57,8 +93,58 @@ mod test_support {
 
         (n * mult).round() / mult
     }
+
+    // Build a hull of a given displacement, waterline length, beam and
+    // draft with a raised forecastle and quarterdeck
+    pub fn get_sized_hull(d: f64, lwl: f64, b: f64, t: f64) -> Hull {
+        let mut hull = Hull::default();
+
+        hull.set_d(d);
+        hull.set_lwl(lwl);
+        hull.b = b;
+        hull.bb = b;
+        hull.t = t;
+        hull.fc_len = 0.20;
+        hull.fc_fwd = 25.0;
+        hull.fc_aft = 22.0;
+        hull.fd_len = 0.30;
+        hull.fd_fwd = 22.0;
+        hull.fd_aft = 20.0;
+        hull.ad_fwd = 20.0;
+        hull.ad_aft = 18.0;
+        hull.qd_len = 0.15;
+        hull.qd_fwd = 18.0;
+        hull.qd_aft = 18.0;
+
+        hull
+    }
+
+    // Build a ship of a given year on a hull, with an engine of the same
+    // year and a battery of (guns, diameter, length, mounts) on deck. No
+    // battery is added if it has no guns.
+    pub fn get_ship(year: u32, hull: Hull, (num, diam, len, mounts): (u32, f64, f64, u32)) -> Ship {
+        let mut ship = Ship { hull, year, ..Default::default() };
+        ship.engine.year = year;
+
+        if num > 0 {
+            let btry = ship.add_battery();
+            btry.num = num;
+            btry.diam = diam;
+            btry.len = len;
+            btry.mount_num = mounts;
+            btry.groups[0].on = mounts;
+        }
+
+        ship
+    }
 }
 
+// Centre {{{1
+/// Label, weight (t), distance forward of midships, height above the keel
+/// and length spread over (ft) of one weight of the ship.
+///
+type Centre = (String, f64, f64, f64, f64);
+
 // Ship {{{1
 /// All the parts of a ship.
 ///
@@ -74,6 +160,9 @@ pub struct Ship {
     pub kind: String,
     /// Year ship laid down
     pub year: u32,
+    /// Surface ship or submarine.
+    #[serde(default)]
+    pub mode: ShipKind,
 
     /// Balance between stability and seakeeping.
     pub trim: u8,
@@ -94,9 +183,23 @@ pub struct Ship {
     pub asw: Vec<ASW>,
     /// Miscellaneous weights.
     pub wgts: MiscWgts,
+    /// Sail rig.
+    #[serde(default)]
+    pub rig: Rig,
+    /// Pressure hull, battery and motors of a submarine.
+    #[serde(default)]
+    pub pressure_hull: PressureHull,
+    /// Aircraft and their handling facilities.
+    #[serde(default)]
+    pub aviation: Aviation,
 
     /// Custom notes
     pub notes: Vec<String>,
+
+    /// User defined loading conditions reported in addition to the
+    /// standard ones.
+    #[serde(default)]
+    pub loads: Vec<LoadType>,
 }
 
 impl Default for Ship { // {{{2
@@ -106,33 +209,151 @@ impl Default for Ship { // {{{2
             country: "".into(),
             kind: "".into(),
             year: 0,
+            mode: ShipKind::default(),
 
             trim: 50,
 
             hull: Hull::default(),
             wgts: MiscWgts::default(),
+            rig: Rig::default(),
+            pressure_hull: PressureHull::default(),
+            aviation: Aviation::default(),
             engine: Engine::default(),
             armor: Armor::default(),
-            torps: vec![Torpedoes::default(), Torpedoes::default()],
+            torps: Vec::new(),
             mines: Mines::default(),
-            asw: vec![ASW::default(), ASW::default()],
-            batteries: vec![
-                Battery::default(),
-                Battery::default(),
-                Battery::default(),
-                Battery::default(),
-                Battery::default(),
-            ],
+            asw: Vec::new(),
+            batteries: Vec::new(),
 
             notes: Vec::new(),
+
+            loads: Vec::new(),
         }
     }
 }
 
 impl Ship { // {{{2
+    /// Number of intervals between light and maximum displacement in
+    /// hydrostatics().
+    const HYDRO_STEPS: u32 = 10;
+
     /// Pounds in a long ton.
     const POUND2TON: f64 = 2240.0;
 
+    /// Number of gun batteries in a SpringSharp file.
+    pub const SS_BATTERIES: usize = 5;
+
+    /// Number of torpedo sets in a SpringSharp file.
+    pub const SS_TORPS: usize = 2;
+
+    /// Number of ASW sets in a SpringSharp file.
+    pub const SS_ASW: usize = 2;
+
+    /// Weight of the 20" torpedo that survivability is measured in (t).
+    const TORP_REF_WGT: f64 = 1.313;
+
+    /// Height of a deck (ft), used for weights above the deck.
+    const DECK_HGT: f64 = 8.0;
+
+    /// Weighting of the rig in stability(), above that of miscellaneous
+    /// weights above the deck.
+    const RIG_STAB: f64 = 6.0;
+
+    /// Weighting of aircraft and their facilities in stability(), the same
+    /// as miscellaneous weights above the deck.
+    const AVN_STAB: f64 = 4.0;
+
+    /// Height of the magazines as a fraction of the height of the deck.
+    const KG_MAG: f64 = 0.25;
+
+    /// Height of fuel and stores as a fraction of the height of the deck.
+    const KG_LOAD: f64 = 0.2;
+
+    /// Largest trim as a fraction of waterline length before ballast is
+    /// needed.
+    const TRIM_MAX: f64 = 0.005;
+
+    /// Distance between the fore and mizzen masts as a fraction of
+    /// waterline length.
+    const RIG_LEN: f64 = 0.6;
+
+    // add_battery {{{3
+    /// Add an empty gun battery after the existing ones and return it.
+    ///
+    pub fn add_battery(&mut self) -> &mut Battery {
+        self.batteries.push(Battery::default());
+        self.batteries.last_mut().unwrap()
+    }
+
+    // remove_battery {{{3
+    /// Remove a gun battery, moving the following batteries up.
+    ///
+    pub fn remove_battery(&mut self, i: usize) -> Option<Battery> {
+        (i < self.batteries.len()).then(|| self.batteries.remove(i))
+    }
+
+    // add_torps {{{3
+    /// Add an empty set of torpedoes after the existing ones and return it.
+    ///
+    pub fn add_torps(&mut self) -> &mut Torpedoes {
+        self.torps.push(Torpedoes::default());
+        self.torps.last_mut().unwrap()
+    }
+
+    // remove_torps {{{3
+    /// Remove a set of torpedoes, moving the following sets up.
+    ///
+    pub fn remove_torps(&mut self, i: usize) -> Option<Torpedoes> {
+        (i < self.torps.len()).then(|| self.torps.remove(i))
+    }
+
+    // add_asw {{{3
+    /// Add an empty set of ASW gear after the existing ones and return it.
+    ///
+    pub fn add_asw(&mut self) -> &mut ASW {
+        self.asw.push(ASW::default());
+        self.asw.last_mut().unwrap()
+    }
+
+    // remove_asw {{{3
+    /// Remove a set of ASW gear, moving the following sets up.
+    ///
+    pub fn remove_asw(&mut self, i: usize) -> Option<ASW> {
+        (i < self.asw.len()).then(|| self.asw.remove(i))
+    }
+
+    // sship_layout {{{3
+    /// Copy of the ship with exactly the number of batteries, groups per
+    /// battery, torpedo sets and ASW sets that a SpringSharp file holds,
+    /// padded with empty ones.
+    ///
+    /// Fails if the ship has more than SpringSharp can store. Empty entries
+    /// beyond the limit are dropped rather than counted.
+    ///
+    pub fn sship_layout(&self) -> Result<Ship, Box<dyn Error>> {
+        fn fit<T: Default + Clone>(v: &[T], n: usize, used: impl Fn(&T) -> bool, what: &str) -> Result<Vec<T>, String> {
+            let used_len = v.iter().rposition(used).map_or(0, |i| i + 1);
+            if used_len > n {
+                return Err(format!("SpringSharp files hold at most {} {}, ship has {}", n, what, used_len));
+            }
+
+            let mut v = v[..used_len].to_vec();
+            v.resize(n, T::default());
+            Ok(v)
+        }
+
+        let mut ship = self.clone();
+
+        ship.batteries = fit(&self.batteries, Self::SS_BATTERIES, |b| b.num > 0, "gun batteries")?;
+        for b in ship.batteries.iter_mut() {
+            b.groups = fit(&b.groups, Battery::SS_GROUPS, |g| g.num_mounts() > 0, "groups per battery")?;
+        }
+        ship.torps     = fit(&self.torps, Self::SS_TORPS, |t| t.num > 0, "torpedo sets")?;
+        ship.asw       = fit(&self.asw, Self::SS_ASW, |a| a.num > 0, "ASW sets")?;
+
+        Ok(ship)
+    }
+
     // year_adj {{{3
     /// Year adjustment factor for various calculations.
     ///
@@ -144,13 +365,14 @@ impl Ship { // {{{2
 
     // deck_space {{{3
     /// Relative measure of hull space based on waterplane area, freeboard and
-    /// displacement adjusted for above water torpedoes.
+    /// displacement adjusted for above water torpedoes and aviation.
     ///
     pub fn deck_space(&self) -> f64 {
         let mut space = 0.0;
         for w in self.torps.iter() {
             space += w.deck_space(self.hull.b); 
         }
+        space += self.aviation.deck_space();
 
         space / self.hull.wp()
     }
@@ -187,7 +409,7 @@ impl Ship { // {{{2
     /// Weight of bunkerage, magazine and stores.
     ///
     fn wgt_load(&self) -> f64 {
-        self.hull.d() * 0.02 + self.wgt_bunker() + self.wgt_mag()
+        self.hull.d() * 0.02 + self.wgt_bunker() + self.wgt_mag() + self.aviation.avgas
     }
 
     // d_lite {{{3
@@ -204,7 +426,7 @@ impl Ship { // {{{2
     /// feedwater.
     ///
     pub fn d_std(&self) -> f64 {
-        self.hull.d() - self.wgt_bunker()
+        self.hull.d() - self.wgt_bunker() - self.aviation.avgas
     }
 
     // d_max {{{3
@@ -212,7 +434,7 @@ impl Ship { // {{{2
     /// feedwater and stores.
     ///
     pub fn d_max(&self) -> f64 {
-        self.hull.d() + 0.8 * self.wgt_bunker()
+        self.hull.d() + 0.8 * (self.wgt_bunker() + self.aviation.avgas)
     }
 
     // t_max {{{3
@@ -229,6 +451,88 @@ impl Ship { // {{{2
         self.hull.cb_calc(self.d_max(), self.t_max())
     }
 
+    // d_load {{{3
+    /// Displacement in a given loading condition.
+    ///
+    pub fn d_load(&self, load: &LoadType) -> f64 {
+        self.d_lite() +
+            self.hull.d() * 0.02 * load.stores() +
+            (self.wgt_bunker() + self.aviation.avgas) * load.bunker() +
+            self.wgt_mag() * load.mag()
+    }
+
+    // t_load {{{3
+    /// Draft in a given loading condition.
+    ///
+    pub fn t_load(&self, load: &LoadType) -> f64 {
+        self.hull.t_calc(self.d_load(load))
+    }
+
+    // loads_all {{{3
+    /// The standard loading conditions followed by any user defined ones.
+    ///
+    pub fn loads_all(&self) -> Vec<LoadType> {
+        let mut loads = vec![LoadType::Light, LoadType::Standard, LoadType::Normal, LoadType::Full];
+        loads.extend(self.loads.iter().cloned());
+
+        loads
+    }
+
+    // capsize_light_warn {{{3
+    /// If a ship that is stable at normal displacement will capsize at light
+    /// displacement.
+    ///
+    fn capsize_light_warn(&self) -> bool {
+        let light = LoadType::Light;
+
+        ! self.capsize_warn() &&
+            self.capsize_warn_calc(self.d_load(&light), self.t_load(&light))
+    }
+
+    // hydrostatics {{{3
+    /// Hydrostatic properties from light to maximum displacement.
+    ///
+    /// The range is divided into HYDRO_STEPS intervals and the displacement
+    /// of each loading condition in loads_all() is always included. Rows are
+    /// in order of increasing displacement.
+    ///
+    pub fn hydrostatics(&self) -> Vec<Hydrostatics> {
+        let (lite, max) = (self.d_lite(), self.d_max());
+
+        let mut conditions: Vec<(String, f64)> = self.loads_all().iter()
+            .map(|load| (load.to_string(), self.d_load(load)))
+            .collect();
+        for i in 1..Self::HYDRO_STEPS {
+            conditions.push(("".into(), lite + (max - lite) * i as f64 / Self::HYDRO_STEPS as f64));
+        }
+        conditions.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        conditions.into_iter().map(|(label, d)| {
+            let t = self.hull.t_calc(d);
+            let cb = self.hull.cb_calc(d, t);
+
+            Hydrostatics {
+                label,
+                d,
+                t,
+                cb,
+                wp: self.hull.wp_calc(cb),
+                metacenter: if t > 0.0 { self.metacenter_calc(d, t) } else { 0.0 },
+                stability: if t > 0.0 { self.stability_adj_calc(d, t) } else { 0.0 },
+            }
+        }).collect()
+    }
+
+    // gun_max {{{3
+    /// Calibre of the largest gun in any battery.
+    ///
+    pub fn gun_max(&self) -> f64 {
+        self.batteries.iter()
+            .filter(|b| b.num > 0)
+            .map(|b| b.diam)
+            .fold(0.0, f64::max)
+    }
+
     // crew_max {{{3
     /// Estimated maximum crew size based on displacement.
     ///
@@ -331,7 +635,7 @@ impl Ship { // {{{2
     /// Cost in millions of US dollars.
     ///
     pub fn cost_dollar(&self) -> f64 {
-        ((self.hull.d()-self.wgt_load())*0.00014+self.wgt_engine()*0.00056+(self.wgt_borne()*8.0)*0.00042)*
+        ((self.hull.d()-self.wgt_load())*0.00014+self.wgt_engine()*0.00056+(self.wgt_borne()*8.0)*0.00042+self.wgt_avn()*0.00042)*
             if self.year as f64 +2.0>1914.0 {
                 1.0+(self.year as f64 +1.5-1914.0)/5.5
             } else { 1.0 }
@@ -362,21 +666,37 @@ impl Ship { // {{{2
     /// A measure of vertical equilibrium.
     ///
     pub fn metacenter(&self) -> f64 {
-        self.hull.b.powf(1.5) * (self.stability_adj() - 0.5) / 0.5 / 200.0
+        self.metacenter_calc(self.hull.d(), self.hull.t)
+    }
+
+    // metacenter_calc {{{3
+    /// Calculate the metacentric height for a given displacement and draft.
+    ///
+    pub fn metacenter_calc(&self, d: f64, t: f64) -> f64 {
+        self.hull.b.powf(1.5) * (self.stability_adj_calc(d, t) - 0.5) / 0.5 / 200.0
     }
 
     // seaboat {{{3
     /// Intermediate calculations for seakeeping() and steadiness().
     ///
     fn seaboat(&self) -> f64 {
-        let a = (self.hull.free_cap(self.cap_calc_broadside()) / (2.4 * self.hull.d().powf(0.2))).sqrt() *
+        self.seaboat_calc(self.hull.d(), self.hull.t)
+    }
+
+    // seaboat_calc {{{3
+    /// Calculate seaboat() for a given displacement and draft.
+    ///
+    /// Freeboard is taken at normal displacement.
+    ///
+    fn seaboat_calc(&self, d: f64, t: f64) -> f64 {
+        let a = (self.hull.free_cap(self.cap_calc_broadside()) / (2.4 * d.powf(0.2))).sqrt() *
             (
-                (self.stability() * 5.0 * (self.hull.bb / self.hull.lwl())).powf(0.2) *
+                (self.stability_calc(d, t) * 5.0 * (self.hull.bb / self.hull.lwl())).powf(0.2) *
                 (self.hull.free_cap(self.cap_calc_broadside()) / self.hull.lwl() * 20.0).sqrt() *
                 (
-                    self.hull.d() /
+                    d /
                         (
-                            self.hull.d() +
+                            d +
                             self.armor.end.wgt(self.hull.lwl(), self.hull.cwp(), self.hull.b) * 3.0 +
                             self.wgt_hull_plus() / 3.0 +
                             (
@@ -387,17 +707,17 @@ impl Ship { // {{{2
                 )
             ) * 8.0;
 
-        let b = a * if (self.hull.t / self.hull.bb) < 0.3 {
-                (self.hull.t / self.hull.bb / 0.3).sqrt()
+        let b = a * if (t / self.hull.bb) < 0.3 {
+                (t / self.hull.bb / 0.3).sqrt()
             } else {
                 1.0
             };
 
         let c = b *
-            if (self.engine.rf_max(self.hull.ws()) / (self.engine.rf_max(self.hull.ws()) + self.engine.rw_max(self.hull.d(), self.hull.lwl(), self.hull.cs()))) < 0.55 &&
+            if (self.engine.rf_max(self.hull.ws()) / (self.engine.rf_max(self.hull.ws()) + self.engine.rw_max(d, self.hull.lwl(), self.hull.cs()))) < 0.55 &&
                 self.engine.vmax > 0.0
             {
-                (self.engine.rf_max(self.hull.ws()) / (self.engine.rf_max(self.hull.ws()) + self.engine.rw_max(self.hull.d(), self.hull.lwl(), self.hull.cs()))).powf(2.0)
+                (self.engine.rf_max(self.hull.ws()) / (self.engine.rf_max(self.hull.ws()) + self.engine.rw_max(d, self.hull.lwl(), self.hull.cs()))).powf(2.0)
             } else {
                 0.3025
             };
@@ -409,7 +729,14 @@ impl Ship { // {{{2
     /// The sea keeping ability of the ship.
     ///
     pub fn seakeeping(&self) -> f64 {
-        self.seaboat() * f64::min(self.steadiness(), 50.0) / 50.0
+        self.seakeeping_calc(self.hull.d(), self.hull.t)
+    }
+
+    // seakeeping_calc {{{3
+    /// Calculate seakeeping() for a given displacement and draft.
+    ///
+    pub fn seakeeping_calc(&self, d: f64, t: f64) -> f64 {
+        self.seaboat_calc(d, t) * f64::min(self.steadiness_calc(d, t), 50.0) / 50.0
     }
 
     // tender_warn {{{3
@@ -427,7 +754,14 @@ impl Ship { // {{{2
     /// If ship will capsize.
     ///
     fn capsize_warn(&self) -> bool {
-        if self.metacenter() <= 0.0 {
+        self.capsize_warn_calc(self.hull.d(), self.hull.t)
+    }
+
+    // capsize_warn_calc {{{3
+    /// If ship will capsize at a given displacement and draft.
+    ///
+    fn capsize_warn_calc(&self, d: f64, t: f64) -> bool {
+        if self.metacenter_calc(d, t) <= 0.0 {
             true
         } else {
             false
@@ -521,7 +855,14 @@ impl Ship { // {{{2
     /// Roll period of the ship.
     ///
     pub fn roll_period(&self) -> f64 {
-        0.42 * self.hull.bb / self.metacenter().sqrt()
+        self.roll_period_calc(self.hull.d(), self.hull.t)
+    }
+
+    // roll_period_calc {{{3
+    /// Calculate the roll period for a given displacement and draft.
+    ///
+    pub fn roll_period_calc(&self, d: f64, t: f64) -> f64 {
+        0.42 * self.hull.bb / self.metacenter_calc(d, t).sqrt()
     }
 
     // steadiness {{{3
@@ -529,7 +870,14 @@ impl Ship { // {{{2
     /// on trim adjustment and seakeeping value.
     ///
     pub fn steadiness(&self) -> f64 {
-        f64::min(self.trim as f64 * self.seaboat(), 100.0)
+        self.steadiness_calc(self.hull.d(), self.hull.t)
+    }
+
+    // steadiness_calc {{{3
+    /// Calculate steadiness() for a given displacement and draft.
+    ///
+    pub fn steadiness_calc(&self, d: f64, t: f64) -> f64 {
+        f64::min(self.trim as f64 * self.seaboat_calc(d, t), 100.0)
     }
 
     // stability {{{3
@@ -537,12 +885,26 @@ impl Ship { // {{{2
     /// the trim adjustment.
     ///
     fn stability(&self) -> f64 {
+        self.stability_calc(self.hull.d(), self.hull.t)
+    }
+
+    // stability_calc {{{3
+    /// Calculate the inherent stability for a given displacement and draft.
+    ///
+    /// This is an approximation: only the displacement and draft vary. The
+    /// vertical moments, including the magazine weight in the armor deck
+    /// and the normal draft used for the hull's freeboard, are always
+    /// those at normal displacement.
+    ///
+    fn stability_calc(&self, d: f64, t: f64) -> f64 {
         let a =
             (self.armor.ct_fwd.wgt(self.hull.d()) + self.armor.ct_aft.wgt(self.hull.d())) * 5.0 +
             (self.wgt_borne() + self.wgt_gun_armor()) * (2.0 * self.gun_super_factor() - 1.0) * 4.0 +
             self.wgts.hull as f64 * 2.0 +
             self.wgts.on as f64 * 3.0 +
             self.wgts.above as f64 * 4.0 +
+            self.wgt_rig() * Self::RIG_STAB +
+            self.wgt_avn() * Self::AVN_STAB +
             self.armor.upper.wgt(self.hull.d(), self.hull.cwp(), self.hull.b) * 2.0 +
             self.armor.main.wgt(self.hull.d(), self.hull.cwp(), self.hull.b) +
             self.armor.end.wgt(self.hull.d(), self.hull.cwp(), self.hull.b) +
@@ -557,7 +919,7 @@ impl Ship { // {{{2
             } else { 0.0 };
 
         if b > 0.0 {
-            ((self.hull.d() * (self.hull.bb / self.hull.t) / b) * 0.5).sqrt() *
+            ((d * (self.hull.bb / t) / b) * 0.5).sqrt() *
             (8.76755 / self.hull.len2beam()).powf(0.25)
         } else {
             b
@@ -569,7 +931,14 @@ impl Ship { // {{{2
     /// on the stability of the ship.
     ///
     pub fn stability_adj(&self) -> f64 {
-        self.stability() * ((50.0 - self.trim as f64) / 150.0 + 1.0)
+        self.stability_adj_calc(self.hull.d(), self.hull.t)
+    }
+
+    // stability_adj_calc {{{3
+    /// Calculate the adjusted stability for a given displacement and draft.
+    ///
+    pub fn stability_adj_calc(&self, d: f64, t: f64) -> f64 {
+        self.stability_calc(d, t) * ((50.0 - self.trim as f64) / 150.0 + 1.0)
     }
 
     // d_factor {{{3
@@ -581,7 +950,8 @@ impl Ship { // {{{2
             self.hull.d() /
             (
                 self.engine.d_engine(self.hull.d(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws()) +
-                    8.0 * self.wgt_borne() + self.wgt_armor() + self.wgts.wgt() as f64
+                    8.0 * self.wgt_borne() + self.wgt_armor() + self.wgts.wgt() as f64 + self.wgt_rig() +
+                    self.wgt_sub() + self.wgt_avn()
             ),
             10.0
         )
@@ -603,16 +973,25 @@ impl Ship { // {{{2
     /// hits required to sink or destroy the ship.
     ///
     pub fn flotation(&self) -> f64 {
+        self.flotation_calc(self.hull.d(), self.hull.t)
+    }
+
+    // flotation_calc {{{3
+    /// Calculate flotation() for a given displacement and draft.
+    ///
+    /// Freeboard is taken at normal displacement.
+    ///
+    pub fn flotation_calc(&self, d: f64, t: f64) -> f64 {
         let a = if self.cap_calc_broadside() {
                 self.hull.free_cap(self.cap_calc_broadside())
             } else {
                 self.hull.freeboard_dist()
             };
 
-        let b = (a * self.hull.wp() / Hull::FT3_PER_TON_SEA + self.hull.d()) / 2.0;
+        let b = (a * self.hull.wp_calc(self.hull.cb_calc(d, t)) / Hull::FT3_PER_TON_SEA + d) / 2.0;
 
-        let c = b * self.stability_adj().powf(
-            if self.stability_adj() > 1.0 { 0.5 } else { 4.0 }
+        let c = b * self.stability_adj_calc(d, t).powf(
+            if self.stability_adj_calc(d, t) > 1.0 { 0.5 } else { 4.0 }
             );
 
         let d = c * if self.str_comp() < 1.0 { self.str_comp() } else { 1.0 };
@@ -693,10 +1072,9 @@ impl Ship { // {{{2
     /// Size of shells used to calculate flotation().
     ///
     pub fn damage_shell_size(&self) -> f64 {
-        if self.batteries[0].diam > 0.0 {
-            self.batteries[0].diam
-        } else {
-            6.0
+        match self.batteries.first() {
+            Some(b) if b.diam > 0.0 => b.diam,
+            _ => 6.0,
         }
     }
 
@@ -705,37 +1083,143 @@ impl Ship { // {{{2
     /// main battery or 6" shells if the ship has no main battery.
     ///
     pub fn damage_shell_num(&self) -> f64 {
-        self.flotation() / (
+        self.damage_shell_num_calc(self.hull.d(), self.hull.t)
+    }
+
+    // damage_shell_num_calc {{{3
+    /// Calculate damage_shell_num() for a given displacement and draft.
+    ///
+    pub fn damage_shell_num_calc(&self, d: f64, t: f64) -> f64 {
+        self.flotation_calc(d, t) / (
             self.damage_shell_size().powf(3.0) /
             2.0 * Self::year_adj(self.year) as f64
             )
     }
 
+    // damage_shell_num_type {{{3
+    /// Number of non-critical hits by main battery shells of a given type,
+    /// or 6" shells if the ship has no main battery.
+    ///
+    pub fn damage_shell_num_type(&self, shell: ShellType) -> f64 {
+        self.damage_shell_num() / (shell.wgt_ratio() * shell.damage_factor())
+    }
+
     // damage_shell_torp_num {{{3
-    /// Number of non-critical 20" torpedo hits required to sink the ship.
+    /// Number of non-critical hits by the ship's own main torpedoes required
+    /// to sink the ship, or 20" torpedo hits if it has none.
     ///
     pub fn damage_torp_num(&self) -> f64 {
+        self.damage_torp_num_calc(self.hull.d(), self.hull.t)
+    }
+
+    // damage_torp_num_calc {{{3
+    /// Calculate damage_torp_num() for a given displacement and draft.
+    ///
+    pub fn damage_torp_num_calc(&self, d: f64, t: f64) -> f64 {
+        self.torps.first()
+            .filter(|torp| torp.num > 0)
+            .and_then(|torp| self.damage_torp_hits_calc(torp, d, t))
+            .unwrap_or_else(|| self.damage_torp_ref_calc(d, t))
+    }
+
+    // damage_torp_hits {{{3
+    /// Number of non-critical hits by a given torpedo required to sink the
+    /// ship, or None if the torpedo has no warhead.
+    ///
+    pub fn damage_torp_hits(&self, torp: &Torpedoes) -> Option<f64> {
+        self.damage_torp_hits_calc(torp, self.hull.d(), self.hull.t)
+    }
+
+    // damage_torp_hits_calc {{{3
+    /// Calculate damage_torp_hits() for a given displacement and draft.
+    ///
+    /// Hits scale with the weight of the torpedo against the 20" reference
+    /// torpedo.
+    ///
+    pub fn damage_torp_hits_calc(&self, torp: &Torpedoes, d: f64, t: f64) -> Option<f64> {
+        if torp.warhead() <= 0.0 || torp.wgt_each() <= 0.0 { return None; }
+
+        Some(self.damage_torp_ref_calc(d, t) * Self::TORP_REF_WGT / torp.wgt_each())
+    }
+
+    // damage_torp_ref_calc {{{3
+    /// Number of non-critical 20" torpedo hits required to sink the ship at
+    /// a given displacement and draft.
+    ///
+    fn damage_torp_ref_calc(&self, d: f64, t: f64) -> f64 {
         (
             (
+                (self.flotation_calc(d, t) / 10_000.0).powf(1.0/3.0) +
+                (self.hull.bb / 75.0).powf(2.0) +
                 (
-                    (self.flotation() / 10_000.0).powf(1.0/3.0) +
-                    (self.hull.bb / 75.0).powf(2.0) +
-                    (
-                        (self.armor.bulkhead.thick / 2.0 * self.armor.bulkhead.len / self.hull.lwl()) /
-                        0.65 * self.armor.bulkhead.hgt / self.hull.t
-                    ).powf(1.0/3.0) *
-                    self.flotation() / 35_000.0 * self.hull.bb / 50.0
-                ) / self.room() * self.hull.lwl() / (self.hull.lwl() + self.hull.bb)
-            ) * if self.stability_adj() < 1.0 {
-                    self.stability_adj().powf(4.0)
-                } else {
-                    1.0
-                } * (1.0 - self.hull_space())
-        ) * if self.torps[0].wgt_weaps() > 0.0 {
-                1.313 / (self.torps[0].wgt_weaps() / self.torps[0].num as f64)
+                    (self.armor.bulkhead.thick / 2.0 * self.armor.bulkhead.len / self.hull.lwl()) /
+                    0.65 * self.armor.bulkhead.hgt / t
+                ).powf(1.0/3.0) *
+                self.flotation_calc(d, t) / 35_000.0 * self.hull.bb / 50.0
+            ) / self.room() * self.hull.lwl() / (self.hull.lwl() + self.hull.bb)
+        ) * if self.stability_adj_calc(d, t) < 1.0 {
+                self.stability_adj_calc(d, t).powf(4.0)
             } else {
                 1.0
-            }
+            } * (1.0 - self.hull_space())
+    }
+
+    // tds_charge {{{3
+    /// Charge (lbs) the torpedo protection on each side withstands without
+    /// flooding the vitals.
+    ///
+    pub fn tds_charge(&self) -> f64 {
+        self.armor.tds_charge(self.hull.b, self.hull.bb)
+    }
+
+    // tds_report {{{3
+    /// Compare the torpedo protection against the torpedoes of a reference
+    /// design.
+    ///
+    pub fn tds_report(&self, reference: &Ship) -> String {
+        let mut r: Vec<String> = Vec::new();
+        let charge = self.tds_charge();
+
+        if charge > 0.0 {
+            r.push(format!("{}: {:.2} ft / {:.2} m deep torpedo protection, {:.0}% of vitals covered",
+                self.name,
+                self.armor.tds_depth(self.hull.b, self.hull.bb),
+                metric(self.armor.tds_depth(self.hull.b, self.hull.bb), LengthLong, self.armor.units),
+                self.armor.tds_coverage(self.hull.lwl()) * 100.0,
+            ));
+            r.push(format!("Withstands a {} lbs / {} kg charge on each side",
+                format_num!(",.0", charge),
+                format_num!(",.0", metric(charge, Weight, self.armor.units)),
+            ));
+        } else {
+            r.push(format!("{}: No torpedo protection", self.name));
+        }
+        r.push("".into());
+
+        let torps: Vec<(usize, &Torpedoes)> = reference.torps.iter().enumerate()
+            .filter(|(_, t)| t.num > 0 && t.warhead() > 0.0)
+            .collect();
+
+        if torps.is_empty() {
+            r.push(format!("{}: No torpedoes", reference.name));
+        } else {
+            r.push(format!("{}:", reference.name));
+        }
+
+        for (i, t) in torps {
+            let warhead = t.warhead();
+
+            r.push(format!("    {}: {:.1}\" ({}) torpedoes, {} lbs / {} kg warhead - {}",
+                ordinal(i),
+                t.diam,
+                t.year,
+                format_num!(",.0", warhead),
+                format_num!(",.0", metric(warhead, Weight, t.units)),
+                if warhead <= charge { "Held" } else { "Vitals flooded" },
+            ));
+        }
+
+        r.join("\n")
     }
 
     // wgt_engine {{{3
@@ -786,7 +1270,10 @@ impl Ship { // {{{2
             self.wgt_armor() -
             self.wgt_engine() -
             self.wgt_load() -
-            self.wgts.wgt() as f64
+            self.wgts.wgt() as f64 -
+            self.wgt_rig() -
+            self.wgt_sub() -
+            self.wgt_avn()
     }
 
     // wgt_hull_plus {{{3
@@ -823,6 +1310,121 @@ impl Ship { // {{{2
         wgt
     }
 
+    // wgt_rig {{{3
+    /// Weight of masts, yards and rigging.
+    ///
+    fn wgt_rig(&self) -> f64 {
+        self.rig.wgt(self.hull.lwl(), self.hull.bb)
+    }
+
+    // sail_speed {{{3
+    /// Speed under sail alone in a fresh breeze.
+    ///
+    pub fn sail_speed(&self) -> f64 {
+        self.engine.speed_at(
+            self.rig.hp(self.hull.lwl(), self.hull.bb),
+            self.hull.d(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws()
+        )
+    }
+
+    // wgt_avn {{{3
+    /// Weight of aircraft, catapults, cranes, hangar and flight deck.
+    ///
+    fn wgt_avn(&self) -> f64 {
+        self.aviation.wgt()
+    }
+
+    // is_submarine {{{3
+    /// Whether the ship is a submarine.
+    ///
+    pub fn is_submarine(&self) -> bool {
+        self.mode == ShipKind::Submarine
+    }
+
+    // wgt_sub {{{3
+    /// Weight of the battery and electric motors of a submarine.
+    ///
+    fn wgt_sub(&self) -> f64 {
+        if self.is_submarine() { self.pressure_hull.wgt() } else { 0.0 }
+    }
+
+    // d_sub {{{3
+    /// Submerged displacement: the surfaced displacement plus the
+    /// watertight volume above the waterline.
+    ///
+    pub fn d_sub(&self) -> f64 {
+        self.hull.d() +
+            self.hull.wp() * self.hull.freeboard_dist() * PressureHull::TIGHT / Hull::FT3_PER_TON_SEA
+    }
+
+    // reserve {{{3
+    /// Reserve buoyancy as a fraction of surfaced displacement.
+    ///
+    pub fn reserve(&self) -> f64 {
+        if self.hull.d() <= 0.0 { return 0.0; } // Catch divide by zero
+
+        self.d_sub() / self.hull.d() - 1.0
+    }
+
+    // test_depth {{{3
+    /// Depth the pressure hull can be taken to (ft).
+    ///
+    pub fn test_depth(&self) -> f64 {
+        self.pressure_hull.test_depth(self.year, self.wgt_hull(), self.hull.lwl(), self.hull.bb)
+    }
+
+    // ws_sub {{{3
+    /// Wetted surface submerged, when the whole girth of the hull is wet.
+    ///
+    fn ws_sub(&self) -> f64 {
+        let depth = self.hull.t + self.hull.freeboard_dist();
+        let girth = 2.0 * self.hull.t + self.hull.bb;
+        if girth <= 0.0 { return 0.0; } // Catch divide by zero
+
+        self.hull.ws() * (2.0 * depth + self.hull.bb) / girth
+    }
+
+    // hp_sub {{{3
+    /// Horsepower needed submerged at a given speed, free of wave making
+    /// but dragged back by the casing.
+    ///
+    fn hp_sub(&self, v: f64) -> f64 {
+        self.engine.hp(v, self.d_sub(), self.hull.lwl(), self.hull.leff(), 0.0, self.ws_sub()) * PressureHull::DRAG
+    }
+
+    // sub_speed {{{3
+    /// Speed submerged on the electric motors.
+    ///
+    pub fn sub_speed(&self) -> f64 {
+        self.engine.speed_at(
+            self.pressure_hull.motor_hp / PressureHull::DRAG,
+            self.d_sub(), self.hull.lwl(), self.hull.leff(), 0.0, self.ws_sub()
+        )
+    }
+
+    // sub_endurance {{{3
+    /// Hours submerged at full speed before the battery is flat.
+    ///
+    pub fn sub_endurance(&self) -> f64 {
+        if self.pressure_hull.motor_hp <= 0.0 { return 0.0; } // Catch divide by zero
+
+        self.pressure_hull.capacity(self.year) * PressureHull::FULL_RATE / self.pressure_hull.motor_hp
+    }
+
+    // sub_range {{{3
+    /// Distance submerged at the economical speed (nm), or at full speed
+    /// if that is slower.
+    ///
+    pub fn sub_range(&self) -> f64 {
+        if self.pressure_hull.motor_hp <= 0.0 { return 0.0; }
+
+        let v = PressureHull::ECON_SPEED.min(self.sub_speed());
+        let hp = self.hp_sub(v);
+        if hp <= 0.0 { return 0.0; } // Catch divide by zero
+
+        self.pressure_hull.capacity(self.year) / hp * v
+    }
+
     // wgt_guns {{{3
     /// Weight of guns (excluding mounts).
     ///
@@ -878,6 +1480,29 @@ impl Ship { // {{{2
         broad
     }
 
+    // shells_min {{{3
+    /// Shells fired per minute by all batteries.
+    ///
+    pub fn shells_min(&self) -> f64 {
+        self.batteries.iter().map(|b| b.shells_min()).sum()
+    }
+
+    // wgt_min {{{3
+    /// Weight of shells fired per minute by all batteries (lbs).
+    ///
+    pub fn wgt_min(&self) -> f64 {
+        self.batteries.iter().map(|b| b.wgt_min()).sum()
+    }
+
+    // aa_score {{{3
+    /// Anti-aircraft effectiveness of all batteries in a range band.
+    ///
+    pub fn aa_score(&self, band: AABand) -> f64 {
+        self.batteries.iter()
+            .filter(|b| b.aa_band() == Some(band))
+            .fold(0.0, |score, b| score + b.aa_score())
+    }
+
     // wgt_armor {{{3
     /// Weight of ship and battery armor.
     ///
@@ -916,15 +1541,24 @@ impl Ship { // {{{2
     /// XXX: I do not know what this does.
     ///
     pub fn super_factor_long(&self) -> f64 {
+        let main = self.batteries.first().cloned().unwrap_or_default();
+
+        // Groups holding mounts, with the position of their guns
+        let placed: Vec<(&SubBattery, f64)> = main.groups.iter().enumerate()
+            .filter(|(_, g)| g.num_mounts() > 0)
+            .map(|(i, g)| (g, match i {
+                0 => g.distribution.g1_gun_position(self.hull.fd_len, self.hull.ad_len()),
+                _ => g.distribution.g2_gun_position(self.hull.fd_len, self.hull.ad_len()),
+            }))
+            .collect();
+
         let a = self.hull_room() *
-            if (
-                    self.batteries[0].groups[0].distribution == GunDistributionType::CenterlineEven ||
-                    self.batteries[0].groups[0].distribution == GunDistributionType::SidesEven ||
-                    self.batteries[0].groups[1].distribution == GunDistributionType::CenterlineEven ||
-                    self.batteries[0].groups[1].distribution == GunDistributionType::SidesEven
+            if main.groups.iter().any(|g|
+                    g.distribution == GunDistributionType::CenterlineEven ||
+                    g.distribution == GunDistributionType::SidesEven
                 ) && (
-                    self.batteries[0].mount_num == 3 ||
-                    self.batteries[0].mount_num == 4
+                    main.mount_num == 3 ||
+                    main.mount_num == 4
                 )
             {
                 self.gun_super_factor()
@@ -932,20 +1566,14 @@ impl Ship { // {{{2
                 1.0
             };
         a *
-            if (
-                    self.batteries[0].groups[0].num_mounts() > 0 &&
-                    self.batteries[0].groups[1].num_mounts() == 0 &&
-                    self.batteries[0].groups[0].distribution.super_factor_long()
-                ) || (
-                    self.batteries[0].groups[1].num_mounts() > 0 &&
-                    self.batteries[0].groups[0].num_mounts() == 0 &&
-                    self.batteries[0].groups[1].distribution.super_factor_long()
-                ) || (
-                    self.batteries[0].groups[0].num_mounts() > 0 &&
-                    self.batteries[0].groups[1].num_mounts() > 0 &&
-                    (self.batteries[0].groups[0].distribution.g1_gun_position(self.hull.fd_len, self.hull.ad_len()) -
-                     self.batteries[0].groups[1].distribution.g2_gun_position(self.hull.fd_len, self.hull.ad_len())).abs() < 0.2
-                )
+            if match placed.as_slice() {
+                    [] => false,
+                    [(g, _)] => g.distribution.super_factor_long(),
+                    _ => {
+                        let (lo, hi) = placed.iter().fold((f64::MAX, f64::MIN), |(lo, hi), (_, p)| (lo.min(*p), hi.max(*p)));
+                        hi - lo < 0.2
+                    },
+                }
             {
                 0.8 * self.gun_super_factor()
             } else {
@@ -953,37 +1581,322 @@ impl Ship { // {{{2
             }
     }
 
-    // percent_calc {{{3
-    /// Return the ratio of a value to the displacement as a percentage.
+    // wgt_dist {{{3
+    /// Distribution of weights at normal displacement.
     ///
-    fn percent_calc(&self, portion: f64) -> String {
-        format!("{} tons, {:.1} %", format_num!(",.0", portion),
-            if self.hull.d() > 0.0 {
-                (portion / self.hull.d()) * 100.0
-            } else {
-                0.0
-            }
-        )
+    /// Each entry is a label and a weight in tons. The groups do not overlap
+    /// so they sum to the normal displacement. Every group is always returned,
+    /// in the same order, even if it has no weight.
+    ///
+    pub fn wgt_dist(&self) -> Vec<(String, f64)> {
+        let lwl = self.hull.lwl();
+        let cwp = self.hull.cwp();
+        let b   = self.hull.b;
+
+        vec![
+            ("Guns".into(), self.wgt_guns() + self.wgt_gun_mounts()),
+            ("Weapons".into(), self.wgt_weaps()),
+            ("Belts".into(),
+                self.armor.main.wgt(lwl, cwp, b) +
+                self.armor.end.wgt(lwl, cwp, b) +
+                self.armor.upper.wgt(lwl, cwp, b)
+            ),
+            ("Torpedo bulkhead".into(), self.armor.bulkhead.wgt(lwl, cwp, b)),
+            (if self.hull.b == self.hull.bb { "Void" } else { "Bulges" }.into(),
+                self.armor.bulge.wgt(lwl, cwp, b)
+            ),
+            ("Gun armour".into(), self.wgt_gun_armor()),
+            // TODO: Replace with the following once the circular references are fixed:
+            // self.armor.deck.wgt(self.hull.clone(), self.wgt_mag(), self.wgt_engine())
+            ("Armour deck".into(), self.armor.deck.wgt(self.hull.clone(), self.wgt_mag(), 0.0)),
+            ("Conning towers".into(),
+                self.armor.ct_fwd.wgt(self.hull.d()) + self.armor.ct_aft.wgt(self.hull.d())
+            ),
+            ("Machinery".into(), self.wgt_engine()),
+            ("Hull, fittings & equipment".into(), self.wgt_hull()),
+            ("Fuel, ammunition & stores".into(), self.wgt_load()),
+            ("Miscellaneous weights".into(), self.wgts.wgt() as f64),
+            ("Masts & rigging".into(), self.wgt_rig()),
+            ("Batteries & motors".into(), self.wgt_sub()),
+            ("Aviation".into(), self.wgt_avn()),
+        ]
     }
 
-    // convert {{{3
-    /// Load a ship from a SpringSharp 3 file and output a sharpie ship
+    // centres {{{3
+    /// Distribution of weights at normal displacement with the position of
+    /// each.
     ///
-    pub fn convert(p: String) -> Result<Ship, Box<dyn Error>> {
-        let mut ship = Ship::default();
+    /// Each entry is a label, a weight in tons, a distance forward of
+    /// midships, a height above the keel and the length the weight is
+    /// spread over, all in feet. As with wgt_dist() the weights sum to the
+    /// normal displacement. Heights are estimated from the draft and the
+    /// height of the deck amidships, and distances from the placing of the
+    /// mounts and the freeboard profile.
+    ///
+    /// Batteries come first, one entry each.
+    ///
+    fn centres(&self) -> Vec<Centre> {
+        let (btrys, mut dist) = self.centres_split();
 
-        let f = File::open(p)?;
-        let reader = BufReader::new(f);
-        let mut lines = reader.lines().map(|l| l.unwrap());
+        let mut all: Vec<Centre> = btrys.into_iter().map(|(_, c)| c).collect();
+        all.append(&mut dist);
 
-        let line = lines.next().unwrap();
-        if line.contains("SpringSharp Version 3.0") {
-            ()
-        } else if line.contains("SpringSharp") {
-            Err("SpringSharp file too old")?;
-        } else {
-            Err("Unknown file format")?;
-        }
+        all
+    }
+
+    // centres_split {{{3
+    /// The entries of centres(), with those of the batteries apart and
+    /// tagged with the index of their battery.
+    ///
+    fn centres_split(&self) -> (Vec<(usize, Centre)>, Vec<Centre>) {
+        let lwl = self.hull.lwl();
+        let cwp = self.hull.cwp();
+        let b   = self.hull.b;
+        let t   = self.hull.t;
+
+        let deck = t + self.hull.freeboard_dist();
+
+        // Mounts are placed from the bow
+        let mounts = Mount::place(self);
+        let mid = |x: f64| lwl / 2.0 - x;
+        let x_of = |battery: Option<usize>| {
+            let (guns, moment) = mounts.iter()
+                .filter(|m| battery.is_none_or(|i| m.battery == i))
+                .fold((0.0, 0.0), |(guns, moment), m| (guns + m.guns as f64, moment + m.guns as f64 * mid(m.x)));
+
+            if guns > 0.0 { moment / guns } else { 0.0 }
+        };
+
+        let btrys: Vec<(usize, Centre)> = self.batteries.iter().enumerate()
+            .filter(|(_, btry)| btry.num > 0)
+            .map(|(i, btry)| (i, (
+                format!("{} battery", ordinal(i)),
+                self.wgt_btry(btry),
+                x_of(Some(i)),
+                btry.kg(deck),
+                0.0,
+            )))
+            .collect();
+
+        // Magazines lie close to the guns they serve but inside the vitals
+        let vitals = self.vitalspace_length();
+        let x_mag = x_of(None).clamp(-vitals / 2.0, vitals / 2.0);
+
+        // Conning towers stand at each end of the superstructure
+        let (ct_fwd, ct_aft) = Mount::super_ends(self);
+
+        // Belts reach about a third of their height below the waterline and
+        // the upper belt sits on top of the main belt
+        let dist = vec![
+            ("Weapons".into(), self.wgt_weaps(), 0.0, deck, lwl),
+            ("Main belt".into(), self.armor.main.wgt(lwl, cwp, b), 0.0, t + self.armor.main.hgt / 6.0,
+                self.armor.main.len
+            ),
+            ("End belts".into(), self.armor.end.wgt(lwl, cwp, b), 0.0, t + self.armor.end.hgt / 6.0, lwl),
+            ("Upper belt".into(), self.armor.upper.wgt(lwl, cwp, b), 0.0,
+                t + self.armor.main.hgt * 2.0 / 3.0 + self.armor.upper.hgt / 2.0, self.armor.upper.len
+            ),
+            ("Torpedo bulkhead".into(), self.armor.bulkhead.wgt(lwl, cwp, b), 0.0,
+                self.armor.bulkhead.hgt.min(deck) / 2.0, self.armor.bulkhead.len
+            ),
+            (if self.hull.b == self.hull.bb { "Void" } else { "Bulges" }.into(),
+                self.armor.bulge.wgt(lwl, cwp, b), 0.0, t / 2.0, self.armor.bulge.len
+            ),
+            // TODO: Replace with the following once the circular references are fixed:
+            // self.armor.deck.wgt(self.hull.clone(), self.wgt_mag(), self.wgt_engine())
+            ("Armour deck".into(), self.armor.deck.wgt(self.hull.clone(), self.wgt_mag(), 0.0), 0.0, deck, vitals),
+            ("Forward conning tower".into(), self.armor.ct_fwd.wgt(self.hull.d()), mid(ct_fwd), deck + Self::DECK_HGT, 0.0),
+            ("Aft conning tower".into(), self.armor.ct_aft.wgt(self.hull.d()), mid(ct_aft), deck + Self::DECK_HGT, 0.0),
+            ("Machinery".into(), self.wgt_engine(), 0.0, deck * 0.35, vitals),
+            ("Hull, fittings & equipment".into(), self.wgt_hull(), self.hull.profile_x() / 2.0, deck * 0.6, lwl),
+            ("Magazines".into(), self.wgt_mag(), x_mag, deck * Self::KG_MAG, vitals),
+            ("Fuel & stores".into(), self.wgt_load() - self.wgt_mag(), 0.0, deck * Self::KG_LOAD, lwl),
+            ("Miscellaneous, vital spaces".into(), self.wgts.vital as f64, 0.0, deck * 0.35, vitals),
+            ("Miscellaneous, hull".into(), self.wgts.hull as f64, 0.0, deck * 0.5, lwl),
+            ("Miscellaneous, on deck".into(), self.wgts.on as f64, 0.0, deck, lwl),
+            ("Miscellaneous, above deck".into(), self.wgts.above as f64, 0.0, deck + Self::DECK_HGT, lwl),
+            ("Miscellaneous, void".into(), self.wgts.void as f64, 0.0, t / 2.0, lwl),
+            ("Masts & rigging".into(), self.wgt_rig(), 0.0,
+                deck + self.rig.hgt(lwl, self.hull.bb), lwl * Self::RIG_LEN
+            ),
+            // Batteries are laid in the bottom of the pressure hull
+            ("Batteries & motors".into(), self.wgt_sub(), 0.0, t * 0.25, vitals),
+            ("Aviation".into(), self.wgt_avn(), 0.0, deck + Self::DECK_HGT, lwl),
+        ];
+
+        (btrys, dist)
+    }
+
+    // wgt_btry {{{3
+    /// Weight of a battery's guns, mounts and mount armour.
+    ///
+    fn wgt_btry(&self, btry: &Battery) -> f64 {
+        btry.gun_wgt() + btry.mount_wgt() + btry.armor_wgt(self.hull.clone())
+    }
+
+    // kg_dist {{{3
+    /// Distribution of weights at normal displacement with the height of
+    /// each above the keel.
+    ///
+    /// Each entry is a label, a weight in tons and a height in feet.
+    ///
+    pub fn kg_dist(&self) -> Vec<(String, f64, f64)> {
+        self.centres().into_iter().map(|(label, w, _, z, _)| (label, w, z)).collect()
+    }
+
+    // lcg_dist {{{3
+    /// Distribution of weights at normal displacement with the distance of
+    /// each forward of midships.
+    ///
+    /// Each entry is a label, a weight in tons and a distance in feet,
+    /// negative abaft midships.
+    ///
+    pub fn lcg_dist(&self) -> Vec<(String, f64, f64)> {
+        self.centres().into_iter().map(|(label, w, x, _, _)| (label, w, x)).collect()
+    }
+
+    // kg {{{3
+    /// Height of the centre of gravity above the keel at normal
+    /// displacement.
+    ///
+    pub fn kg(&self) -> f64 {
+        let (wgt, moment) = self.kg_dist().iter()
+            .fold((0.0, 0.0), |(wgt, moment), (_, w, z)| (wgt + w, moment + w * z));
+
+        if wgt <= 0.0 { return 0.0; } // Catch divide by zero
+
+        moment / wgt
+    }
+
+    // lcg {{{3
+    /// Distance of the centre of gravity forward of midships at normal
+    /// displacement.
+    ///
+    pub fn lcg(&self) -> f64 {
+        let (wgt, moment) = self.lcg_dist().iter()
+            .fold((0.0, 0.0), |(wgt, moment), (_, w, x)| (wgt + w, moment + w * x));
+
+        if wgt <= 0.0 { return 0.0; } // Catch divide by zero
+
+        moment / wgt
+    }
+
+    // trim {{{3
+    /// Trim at normal displacement (ft), positive by the head.
+    ///
+    /// The centre of buoyancy is taken to be amidships, so the ship trims
+    /// until the centre of gravity lies over it.
+    ///
+    pub fn trim(&self) -> f64 {
+        let bml = self.hull.bml();
+        if bml <= 0.0 { return 0.0; } // Catch divide by zero
+
+        self.lcg() * self.hull.lwl() / bml
+    }
+
+    // trim_warn {{{3
+    /// Does the ship trim enough to need ballast?
+    ///
+    pub fn trim_warn(&self) -> bool {
+        self.trim().abs() > self.hull.lwl() * Self::TRIM_MAX
+    }
+
+    // trim_desc {{{3
+    /// Describe a trim and a position of the centre of gravity (ft, positive
+    /// by the head and forward of midships) to the tenth of a foot.
+    ///
+    fn trim_desc(trim: f64, lcg: f64) -> String {
+        let trim = if (trim * 10.0).round() == 0.0 {
+            "on an even keel".into()
+        } else {
+            format!("{:.1} ft / {:.1} m by the {}",
+                trim.abs(),
+                metric(trim.abs(), LengthLong, Imperial),
+                if trim > 0.0 { "head" } else { "stern" }
+            )
+        };
+        let lcg = if (lcg * 10.0).round() == 0.0 {
+            "at midships".into()
+        } else {
+            format!("{:.1} ft / {:.1} m {} midships",
+                lcg.abs(),
+                metric(lcg.abs(), LengthLong, Imperial),
+                if lcg > 0.0 { "forward of" } else { "abaft" }
+            )
+        };
+
+        format!("{}, centre of gravity {}", trim, lcg)
+    }
+
+    // kg_load {{{3
+    /// Height of the centre of gravity above the keel in a given loading
+    /// condition.
+    ///
+    pub fn kg_load(&self, load: &LoadType) -> f64 {
+        let d = self.d_load(load);
+        if d <= 0.0 { return 0.0; } // Catch divide by zero
+
+        let deck = self.hull.t + self.hull.freeboard_dist();
+
+        // Only fuel, stores and magazines change from normal displacement
+        let stores = self.hull.d() * 0.02 * (load.stores() - 1.0);
+        let bunker = self.wgt_bunker() * (load.bunker() - 1.0);
+        let mag    = self.wgt_mag() * (load.mag() - 1.0);
+
+        (
+            self.kg() * self.hull.d() +
+            (stores + bunker) * deck * Self::KG_LOAD +
+            mag * deck * Self::KG_MAG
+        ) / d
+    }
+
+    // gm {{{3
+    /// Metacentric height from the heights of the ship's weights, as
+    /// opposed to the SpringSharp figure of metacenter().
+    ///
+    pub fn gm(&self) -> f64 {
+        self.hull.km() - self.kg()
+    }
+
+    // percent_calc {{{3
+    /// Return the ratio of a value to the displacement as a percentage.
+    ///
+    fn percent_calc(&self, portion: f64) -> String {
+        format!("{} tons, {:.1} %", format_num!(",.0", portion),
+            if self.hull.d() > 0.0 {
+                (portion / self.hull.d()) * 100.0
+            } else {
+                0.0
+            }
+        )
+    }
+
+    // convert {{{3
+    /// Load a ship from a SpringSharp 3 file and output a sharpie ship
+    ///
+    pub fn convert(p: String) -> Result<Ship, Box<dyn Error>> {
+        let mut ship = Ship {
+            batteries: vec![Battery::default(); Self::SS_BATTERIES],
+            torps: vec![Torpedoes::default(); Self::SS_TORPS],
+            asw: vec![ASW::default(); Self::SS_ASW],
+            ..Default::default()
+        };
+        for b in ship.batteries.iter_mut() {
+            b.groups.resize(Battery::SS_GROUPS, SubBattery::default());
+        }
+
+        let f = File::open(p)?;
+        let reader = BufReader::new(f);
+        let mut lines = reader.lines().map(|l| l.unwrap());
+
+        let line = lines.next().unwrap();
+        if line.contains("SpringSharp Version 3.0") {
+            ()
+        } else if line.contains("SpringSharp") {
+            Err("SpringSharp file too old")?;
+        } else {
+            Err("Unknown file format")?;
+        }
 
         ship.name    = lines.next().unwrap();
         ship.country = lines.next().unwrap();
@@ -1201,9 +2114,8 @@ impl Ship { // {{{2
         // SpringSharp does not store the number of mounts in Group 0 that
         // are on the deck so we have to calculate it from the other numbers
         for b in ship.batteries.iter_mut() {
-            b.groups[0].on = b.mount_num -
-                b.groups[0].above - b.groups[0].below -
-                b.groups[1].above - b.groups[1].on - b.groups[1].below;
+            b.groups[0].on = 0;
+            b.groups[0].on = b.mount_num - b.group_mounts();
         }
 
         // SpringSharp uses hull year for torpedo, mine and ASW year
@@ -1266,25 +2178,26 @@ impl Ship { // {{{2
     fn ship_type(&self) -> String {
         let mut s: Vec<String> = Vec::new();
 
-        let main = self.batteries[0].clone();
-        let sec = self.batteries[1].clone();
-        let ter = self.batteries[2].clone();
+        let main = self.batteries.first().cloned().unwrap_or_default();
+        let sec = self.batteries.get(1).cloned().unwrap_or_default();
+        let ter = self.batteries.get(2).cloned().unwrap_or_default();
 
         if main.mount_kind == MountType::OpenBarbette ||
             sec.mount_kind == MountType::OpenBarbette
         { s.push("Barbette Ship".into()); }
 
-        if main.groups[0].distribution == GunDistributionType::CenterlineFD ||
-            main.groups[0].distribution == GunDistributionType::SidesEndsFD
-        { s.push("Central Citadel Ship".into()); }
+        if main.groups.first().is_some_and(|g|
+            g.distribution == GunDistributionType::CenterlineFD ||
+            g.distribution == GunDistributionType::SidesEndsFD
+        ) { s.push("Central Citadel Ship".into()); }
 
         let main_broad = main.mount_kind == MountType::Broadside;
         let sec_broad  = sec.mount_kind == MountType::Broadside;
         let ter_broad  = ter.mount_kind == MountType::Broadside;
 
-        let main_below = (main.groups[0].below + main.groups[1].below) > 0;
-        let sec_below  = (sec.groups[0].below + main.groups[1].below) > 0;
-        let ter_below  = (ter.groups[0].below + main.groups[1].below) > 0;
+        let main_below = main.mounts_below() > 0;
+        let sec_below  = sec.mounts_below() > 0;
+        let ter_below  = ter.mounts_below() > 0;
 
         let main_broad_below = main_broad && main_below;
         let sec_broad_below  = sec_broad  && sec_below;
@@ -1369,6 +2282,21 @@ macro_rules! num {
     }
 }
 
+// ordinal {{{3
+/// Label for the nth battery or set of weapons: "Main", "2nd", "3rd", ...
+///
+fn ordinal(i: usize) -> String {
+    let n = i + 1;
+    match (n % 10, n % 100) {
+        _ if i == 0     => "Main".to_string(),
+        (_, 11..=13)    => format!("{}th", n),
+        (1, _)          => format!("{}st", n),
+        (2, _)          => format!("{}nd", n),
+        (3, _)          => format!("{}rd", n),
+        _               => format!("{}th", n),
+    }
+}
+
 // plural {{{3
 /// Return an "s" is num is anything other than 1.
 ///
@@ -1402,20 +2330,32 @@ impl Ship { // {{{3
             { addto!(r, "DESIGN FAILURE: Gun weight too much for hull"); }
         if self.wgt_armor() > self.hull.d()
             { addto!(r, "DESIGN FAILURE: Armour weight too much for hull"); }
+        if !self.aviation.deck_fits(self.hull.loa(), self.hull.b)
+            { addto!(r, "DESIGN FAILURE: Flight deck too large for hull"); }
         if self.str_comp() < 0.5
             { addto!(r, "DESIGN FAILURE: Overall load weight too much for hull"); }
         if self.capsize_warn()
             { addto!(r, "DESIGN FAILURE: Ship will capsize"); }
+        if self.capsize_light_warn()
+            { addto!(r, "DESIGN FAILURE: Ship will capsize at light displacement"); }
 
         addto!(r);
 
         addto!(r, "Displacement:"); // {{{5
-        addto!(r, "    {} t light; {} t standard; {} t normal; {} t full load",
-            num!(self.d_lite(), 0),
-            num!(self.d_std(), 0),
-            num!(self.hull.d(), 0),
-            num!(self.d_max(), 0)
-        );
+        if self.is_submarine() {
+            addto!(r, "    {} t surfaced; {} t submerged; reserve buoyancy {:.0}%",
+                num!(self.hull.d(), 0),
+                num!(self.d_sub(), 0),
+                self.reserve() * 100.0
+            );
+        } else {
+            addto!(r, "    {} t light; {} t standard; {} t normal; {} t full load",
+                num!(self.d_lite(), 0),
+                num!(self.d_std(), 0),
+                num!(self.hull.d(), 0),
+                num!(self.d_max(), 0)
+            );
+        }
         addto!(r);
 
         addto!(r, "Dimensions: Length (overall / waterline) x beam x draught (normal/deep)"); // {{{5
@@ -1438,6 +2378,7 @@ impl Ship { // {{{3
         addto!(r);
 
         addto!(r, "Armament:"); // {{{5
+        let mounts = Mount::place(self);
         for (i, b) in self.batteries.iter().enumerate() {
             let main_gun = i == 0;
 
@@ -1452,6 +2393,9 @@ impl Ship { // {{{3
                 num!(metric(b.shell_wgt(), Weight, b.units), 2),
                 num!(b.shells, 0),
             );
+            if b.shells > 0 && !b.loadout.is_empty() {
+                addto!(r, "        Magazine {}", b.mag_desc());
+            }
             addto!(r, "        {} gun{} in {} mount{}, {} Model",
                 b.kind,
                 plural(b.num),
@@ -1459,13 +2403,32 @@ impl Ship { // {{{3
                 plural(b.num),
                 b.year
             );
+            addto!(r, "        {} rounds per minute per gun, {} shells / {} lbs / {} kg per minute",
+                num!(b.rof(), 1),
+                num!(b.shells_min(), 1),
+                num!(b.wgt_min(), 0),
+                num!(metric(b.wgt_min(), Weight, b.units), 0),
+            );
+            addto!(r, "        Maximum range {} yds / {} m at {:.0} degrees elevation",
+                num!(b.max_range(), 0),
+                num!(metric(b.max_range() * 3.0, LengthLong, Imperial), 0),
+                b.max_elevation,
+            );
+
+            if b.group_mounts() != b.mount_num {
+                addto!(r, "        WARNING: {} of {} mounts placed in groups",
+                    b.group_mounts(),
+                    b.mount_num,
+                );
+            }
 
+            let above: u32 = b.groups.iter().map(|g| g.above).sum();
             for (i, sb) in b.groups.iter().enumerate() {
                 let sb_super = match i {
-                    0 => sb.above < (b.mount_num - b.groups[1].above),
-                    // TODO: SpringSharp BUG. Correct line is the below commented line:
-                    // 1 => sb.above < (b.mount_num - b.groups[0].above),
-                    _ => sb.above < (2 * sb.num_mounts() - sb.above),
+                    // TODO: SpringSharp BUG. The second group should use the
+                    // same test as the others.
+                    1 => sb.above < (2 * sb.num_mounts() - sb.above),
+                    _ => sb.above < b.mount_num.saturating_sub(above - sb.above),
                 };
 
                 if sb.num_mounts() == 0 { continue; }
@@ -1528,19 +2491,48 @@ impl Ship { // {{{3
                     );
                 }
             }
+
+            let bearing = Bearing::of(&mounts, i);
+            addto!(r, "        Guns bearing: {} ahead, {} astern, {} to port, {} to starboard",
+                bearing.ahead,
+                bearing.astern,
+                bearing.port,
+                bearing.starboard,
+            );
         }
         addto!(r, "    Weight of broadside {} lbs / {} kg",
             num!(self.wgt_broad(), 0),
             num!(metric(self.wgt_broad(), Weight, Imperial), 0),
         );
+        addto!(r, "    Weight of fire per minute {} lbs / {} kg, {} shells",
+            num!(self.wgt_min(), 0),
+            num!(metric(self.wgt_min(), Weight, Imperial), 0),
+            num!(self.shells_min(), 0),
+        );
+
+        // Anti-aircraft {{{5
+        if self.batteries.iter().any(|b| b.aa_band().is_some()) {
+            addto!(r, "Anti-aircraft defence:");
+            for band in [AABand::Long, AABand::Medium, AABand::Short] {
+                let guns: u32 = self.batteries.iter()
+                    .filter(|b| b.aa_band() == Some(band))
+                    .map(|b| b.num)
+                    .sum();
+
+                addto!(r, "    {}: {:.1} ({} gun{})",
+                    band,
+                    self.aa_score(band),
+                    guns,
+                    plural(guns),
+                );
+            }
+        }
 
         // Weapons {{{5
         for (i, torp) in self.torps.iter().enumerate() {
             if torp.num == 0 { continue; }
 
-            addto!(r, "{} Torpedoes",
-                match i { 0 => "Main", 1 => "2nd", _ => "Other", }
-            );
+            addto!(r, "{} Torpedoes", ordinal(i));
             addto!(r, "{} - {:.1}\" / {:.0} mm, {:.2} ft / {:.2} m torpedo{} {:.3} t total",
                 torp.num,
                 torp.diam,
@@ -1556,6 +2548,13 @@ impl Ship { // {{{3
             addto!(r, "    {}",
                 torp.mount_kind.desc(torp.num, torp.mounts)
             );
+            if torp.warhead() > 0.0 {
+                addto!(r, "    {} lbs / {} kg warhead, {}",
+                    num!(torp.warhead(), 0),
+                    num!(metric(torp.warhead(), Weight, torp.units), 0),
+                    torp.settings_desc(),
+                );
+            }
         }
 
         if self.mines.num != 0 {
@@ -1575,9 +2574,7 @@ impl Ship { // {{{3
         for (i, asw) in self.asw.iter().enumerate() {
             if asw.num == 0 { continue; }
 
-            addto!(r, "{} DC/AS Mortars",
-                match i { 0 => "Main", 1 => "2nd", _ => "Other", }
-            );
+            addto!(r, "{} DC/AS Mortars", ordinal(i));
             addto!(r, "{} - {:.2} lbs / {:.2} kg {}{} - {:.3} t total",
                 asw.num,
                 asw.wgt,
@@ -1593,153 +2590,68 @@ impl Ship { // {{{3
             }
         }
 
-        // Armor {{{5
-        addto!(r);
-        addto!(r, "Armour:");
-
-        if self.armor.main.thick + self.armor.end.thick + self.armor.upper.thick + self.armor.bulkhead.thick > 0.0 {
-            addto!(r, " - Belts:    Width (max)    Length (avg)    Height (avg)");
-            if self.armor.main.thick > 0.0 {
-                addto!(r, "    Main:    {}\" / {:.0} mm    {:.2} ft / {:.2} m    {:.2} ft / {:.2} m",
-                    num!(self.armor.main.thick, if self.armor.main.thick < 10.0 { 2 } else { 1 }),
-                    metric(self.armor.main.thick, LengthSmall, self.armor.units),
-                    self.armor.main.len,
-                    metric(self.armor.main.len, LengthLong, self.armor.units),
-                    self.armor.main.hgt,
-                    metric(self.armor.main.hgt, LengthLong, self.armor.units),
-                );
-            }
-
-            if self.armor.end.thick > 0.0 {
-                addto!(r, "    Ends:    {}\" / {:.0} mm    {:.2} ft / {:.2} m    {:.2} ft / {:.2} m",
-                    num!(self.armor.end.thick, if self.armor.end.thick < 10.0 { 2 } else { 1 }),
-                    metric(self.armor.end.thick, LengthSmall, self.armor.units),
-                    self.armor.end.len,
-                    metric(self.armor.end.len, LengthLong, self.armor.units),
-                    self.armor.end.hgt,
-                    metric(self.armor.end.hgt, LengthLong, self.armor.units),
-                );
-                if self.armor.main.len + self.armor.end.len < self.hull.lwl() {
-                    addto!(r, "    {:.2} ft / {:.2} m Unarmoured ends",
-                        self.hull.lwl() - self.armor.main.len - self.armor.end.len,
-                        metric(self.hull.lwl() - self.armor.main.len - self.armor.end.len, LengthLong, self.armor.units)
-                    );
-                }
-            } else if self.armor.main.len < self.hull.lwl() {
-                addto!(r, "    Ends:    Unarmoured");
-            }
-
-            if self.armor.upper.thick > 0.0 {
-                addto!(r, "    Upper:    {}\" / {:.0} mm    {:.2} ft / {:.2} m    {:.2} ft / {:.2} m",
-                    num!(self.armor.upper.thick, if self.armor.upper.thick < 10.0 { 2 } else { 1 }),
-                    metric(self.armor.upper.thick, LengthSmall, self.armor.units),
-                    self.armor.upper.len,
-                    metric(self.armor.upper.len, LengthLong, self.armor.units),
-                    self.armor.upper.hgt,
-                    metric(self.armor.upper.hgt, LengthLong, self.armor.units),
+        // Aviation {{{5
+        if !self.aviation.is_empty() {
+            addto!(r);
+            addto!(r, "Aviation:");
+            if self.aviation.aircraft > 0 {
+                addto!(r, "    {} aircraft of {} lbs / {} kg - {} t total",
+                    self.aviation.aircraft,
+                    num!(self.aviation.aircraft_wgt, 0),
+                    num!(metric(self.aviation.aircraft_wgt, Weight, Imperial), 0),
+                    num!(self.aviation.wgt_aircraft(), 1)
                 );
             }
-
-            if self.armor.main.thick > 0.0 {
-                addto!(r, "    Main Belt covers {:.0} % of normal length",
-                    self.armor.belt_coverage(self.hull.lwl())*100.0
+            if self.aviation.catapults + self.aviation.cranes > 0 {
+                addto!(r, "    {} catapult{}, {} crane{}",
+                    self.aviation.catapults,
+                    plural(self.aviation.catapults),
+                    self.aviation.cranes,
+                    plural(self.aviation.cranes)
                 );
-                if self.armor.belt_coverage(self.hull.lwl()) < self.hull_room() {
-                    addto!(r, "    Main belt does not fully cover magazines and engineering spaces");
-                }
             }
-
-            if self.armor.incline != 0.0 {
-                addto!(r, "    Main Belt inclined {:.2} degrees (positive = in)",
-                    self.armor.incline
+            if self.aviation.hangar > 0.0 {
+                addto!(r, "    Hangar of {} cu ft / {} cu m",
+                    num!(self.aviation.hangar, 0),
+                    num!(metric(self.aviation.hangar, Volume, Imperial), 0)
                 );
             }
-
-            if self.armor.bulkhead.thick > 0.0 {
-                addto!(r);
-                addto!(r, "- Torpedo Bulkhead - {} bulkheads:",
-                    match self.armor.bh_kind {
-                        BulkheadType::Strengthened => "Strengthened structural",
-                        BulkheadType::Additional   => "Additional damage containing",
-                    }
-                );
-                addto!(r, "        {}\" / {:.0} mm    {:.2} ft / {:.2} m    {:.2} ft / {:.2} m",
-                    num!(self.armor.bulkhead.thick, if self.armor.bulkhead.thick < 10.0 { 2 } else { 1 }),
-                    metric(self.armor.bulkhead.thick, LengthSmall, self.armor.units),
-                    self.armor.bulkhead.len,
-                    metric(self.armor.bulkhead.len, LengthLong, self.armor.units),
-                    self.armor.bulkhead.hgt,
-                    metric(self.armor.bulkhead.hgt, LengthLong, self.armor.units),
-                );
-                addto!(r, "    Beam between torpedo bulkheads {:.2} ft / {:.2} m",
-                    self.armor.bh_beam,
-                    metric(self.armor.bh_beam, LengthLong, self.armor.units)
+            if self.aviation.deck_len * self.aviation.deck_wid > 0.0 {
+                addto!(r, "    Flight deck {:.2} ft x {:.2} ft / {:.2} m x {:.2} m - {} t",
+                    self.aviation.deck_len,
+                    self.aviation.deck_wid,
+                    metric(self.aviation.deck_len, LengthLong, Imperial),
+                    metric(self.aviation.deck_wid, LengthLong, Imperial),
+                    num!(self.aviation.wgt_deck(), 0)
                 );
-                addto!(r);
             }
-
-            if self.armor.bulge.thick > 0.0 || self.wgts.void > 0 {
-                addto!(r, "- Hull {}:",
-                    if self.hull.b == self.hull.bb { "void" }
-                    else { "Bulges" }
-                );
-                addto!(r, "        {}\" / {:.0} mm    {:.2} ft / {:.2} m    {:.2} ft / {:.2} m",
-                    num!(self.armor.bulge.thick, if self.armor.bulge.thick < 10.0 { 2 } else { 1 }),
-                    metric(self.armor.bulge.thick, LengthSmall, self.armor.units),
-                    self.armor.bulge.len,
-                    metric(self.armor.bulge.len, LengthLong, self.armor.units),
-                    self.armor.bulge.hgt,
-                    metric(self.armor.bulge.hgt, LengthLong, self.armor.units),
+            if self.aviation.avgas > 0.0 {
+                addto!(r, "    {} tons of aviation {}",
+                    num!(self.aviation.avgas, 0),
+                    FuelType::Gasoline.name()
                 );
-            addto!(r);
             }
         }
 
-        if self.wgt_gun_armor() > 0.0 {
-            addto!(r, "- Gun armour:    Face (max)    Other gunhouse (avg)    Barbette/hoist (max)");
-
-            for (i, b) in self.batteries.iter().enumerate() {
-                if b.armor_face == 0.0 &&
-                b.armor_back == 0.0 &&
-                b.armor_barb == 0.0 { continue; }
-                addto!(r, "    {}:    {}        {}            {}",
-                    match i { 0 => "Main", 1 => "2nd", 2 => "3rd", 3 => "4th", 4 => "5th", _ => "Other", },
-                    if b.armor_face == 0.0 { "-".into() } else { format!("{}\" / {:.0} mm", num!(b.armor_face, if b.armor_face >= 10.0 { 1 } else { 2 }), metric(b.armor_face, LengthSmall, b.units)) },
-                    if b.armor_back == 0.0 { "-".into() } else { format!("{}\" / {:.0} mm", num!(b.armor_back, if b.armor_back >= 10.0 { 1 } else { 2 }), metric(b.armor_back, LengthSmall, b.units)) },
-                    if b.armor_barb == 0.0 { "-".into() } else { format!("{}\" / {:.0} mm", num!(b.armor_barb, if b.armor_barb >= 10.0 { 1 } else { 2 }), metric(b.armor_barb, LengthSmall, b.units)) },
-                );
-            }
+        // Armor {{{5
+        if self.is_submarine() {
             addto!(r);
-        }
-
-        if self.armor.deck.fc + self.armor.deck.md + self.armor.deck.qd > 0.0 {
-            addto!(r, "- {}:",
-                self.armor.deck.kind
-            );
-            // TODO: Change spelling to Fore (required to match Springsharp reports)
-            addto!(r, "    For and Aft decks: {:.2}\" / {:.0} mm",
-                self.armor.deck.md,
-                metric(self.armor.deck.md, LengthSmall, self.armor.units)
-            );
-            // TODO: Change spelling to Quarterdeck (required to match Springsharp reports)
-            addto!(r, "    Forecastle: {:.2}\" / {:.0} mm    Quarter deck: {:.2}\" / {:.0} mm",
-                self.armor.deck.fc,
-                metric(self.armor.deck.fc, LengthSmall, self.armor.units),
-                self.armor.deck.qd,
-                metric(self.armor.deck.qd, LengthSmall, self.armor.units)
+            addto!(r, "Pressure hull:");
+            addto!(r, "    {:.2} ft / {:.2} m diameter x {:.2} ft / {:.2} m long, {:.2}\" / {:.0} mm plating",
+                self.pressure_hull.diameter(self.hull.bb),
+                metric(self.pressure_hull.diameter(self.hull.bb), LengthLong, Imperial),
+                self.pressure_hull.length(self.hull.lwl()),
+                metric(self.pressure_hull.length(self.hull.lwl()), LengthLong, Imperial),
+                self.pressure_hull.plating(self.wgt_hull(), self.hull.lwl(), self.hull.bb),
+                metric(self.pressure_hull.plating(self.wgt_hull(), self.hull.lwl(), self.hull.bb), LengthSmall, Imperial)
             );
-            addto!(r);
-        }
-
-        if self.armor.ct_fwd.thick + self.armor.ct_aft.thick > 0.0 {
-            // TODO: Remove stray space before comma (required to match Springsharp reports)
-            addto!(r, "- Conning towers: Forward {:.2}\" / {:.0} mm, Aft {:.2}\" / {:.0} mm",
-                self.armor.ct_fwd.thick,
-                metric(self.armor.ct_fwd.thick, LengthSmall, self.armor.units),
-                self.armor.ct_aft.thick,
-                metric(self.armor.ct_aft.thick, LengthSmall, self.armor.units)
+            addto!(r, "    Test depth {} ft / {} m",
+                num!(self.test_depth(), 0),
+                num!(metric(self.test_depth(), LengthLong, Imperial), 0)
             );
             addto!(r);
+        } else {
+            self.report_armour(&mut r);
         }
 
         addto!(r, "Machinery:"); // {{{5
@@ -1757,13 +2669,48 @@ impl Ship { // {{{3
                 num!(metric(self.engine.hp_max(self.hull.d(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws()), Power, Imperial), 0),
                 self.engine.vmax
             );
+            for p in self.engine.plants.iter() {
+                addto!(r, "    {} plant: {}{}, {}, {} {} / {} Kw",
+                    p.role,
+                    p.fuel,
+                    if p.fuel.is_steam() { format!(", {}", p.boiler) } else { "".into() },
+                    p.drive,
+                    num!(p.hp, 0),
+                    p.hp_type(),
+                    num!(metric(p.hp, Power, Imperial), 0)
+                );
+            }
             addto!(r, "    Range {}nm at {:.2} kts",
                 num!(self.engine.range, 0),
                 self.engine.vcruise
             );
+            if !self.engine.plants.is_empty() {
+                for m in self.engine.modes(self.hull.d(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws()) {
+                    addto!(r, "        - {}: {} {} = {:.2} kts, range {}nm at {:.2} kts",
+                        m.label,
+                        num!(m.hp, 0),
+                        m.hp_type,
+                        m.speed,
+                        num!(m.range, 0),
+                        self.engine.vcruise.min(m.speed)
+                    );
+                }
+            }
             addto!(r, "    Bunker at max displacement = {} tons{}",
                 num!(self.engine.bunker_max(self.hull.d(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws()), 0),
-                if self.engine.pct_coal > 0.0 { format!(" ({:.0}% coal)", self.engine.pct_coal * 100.0) } else { "".into() }
+                if !self.engine.plants.is_empty() {
+                    format!(" ({})",
+                        self.engine.bunker_fuels(self.hull.d(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws())
+                            .iter()
+                            .map(|(fuel, w)| format!("{} tons {}", num!(w * 1.8, 0), fuel.name()))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    )
+                } else if self.engine.pct_coal > 0.0 {
+                    format!(" ({:.0}% coal)", self.engine.pct_coal * 100.0)
+                } else {
+                    "".into()
+                }
             );
             let ratio = self.engine.hp_max(self.hull.d(), self.hull.lwl(), self.hull.leff(), self.hull.cs(), self.hull.ws()) / self.engine.shafts() as f64;
 
@@ -1776,9 +2723,31 @@ impl Ship { // {{{3
                 addto!(r, "    Caution: Delicate, lightweight machinery.");
             }
 
-        } else {
+        } else if self.rig.kind == RigType::None {
             addto!(r, "    Immobile floating battery");
         }
+        if self.is_submarine() {
+            addto!(r, "    Electric motors, {} hp / {} Kw, {} tons of batteries = {:.2} kts submerged",
+                num!(self.pressure_hull.motor_hp, 0),
+                num!(metric(self.pressure_hull.motor_hp, Power, Imperial), 0),
+                num!(self.pressure_hull.battery, 0),
+                self.sub_speed()
+            );
+            addto!(r, "    Submerged endurance {:.1} hours at full speed, range {}nm at {:.2} kts",
+                self.sub_endurance(),
+                num!(self.sub_range(), 0),
+                PressureHull::ECON_SPEED.min(self.sub_speed())
+            );
+        }
+        if self.rig.kind != RigType::None {
+            addto!(r, "    {} rig, {} sq ft / {} sq m of sail, {} tons of masts and rigging = {:.2} kts under sail",
+                self.rig.kind,
+                num!(self.rig.sail_area(self.hull.lwl(), self.hull.bb), 0),
+                num!(metric(self.rig.sail_area(self.hull.lwl(), self.hull.bb), Area, Imperial), 0),
+                num!(self.wgt_rig(), 0),
+                self.sail_speed()
+            );
+        }
         addto!(r);
 
         addto!(r, "Complement:"); // {{{5
@@ -1806,9 +2775,9 @@ impl Ship { // {{{3
             );
         }
 
-        if self.torps[0].wgt() + self.torps[1].wgt() + self.mines.wgt() + self.asw[0].wgt() + self.asw[1].wgt > 0.0 {
+        if self.wgt_weaps() > 0.0 {
             addto!(r, "    - Weapons: {}",
-                self.percent_calc(self.torps[0].wgt() + self.torps[1].wgt() + self.mines.wgt() + self.asw[0].wgt() + self.asw[1].wgt()),
+                self.percent_calc(self.wgt_weaps()),
             );
         }
 
@@ -1865,6 +2834,16 @@ impl Ship { // {{{3
         addto!(r, "    Machinery: {}",
             self.percent_calc(self.wgt_engine()),
         );
+        if self.wgt_sub() > 0.0 {
+            addto!(r, "    Batteries & motors: {}",
+                self.percent_calc(self.wgt_sub()),
+            );
+        }
+        if self.wgt_avn() > 0.0 {
+            addto!(r, "    Aviation: {}",
+                self.percent_calc(self.wgt_avn()),
+            );
+        }
         addto!(r, "    Hull, fittings & equipment: {}",
             self.percent_calc(self.wgt_hull()),
         );
@@ -1892,6 +2871,186 @@ impl Ship { // {{{3
 
         addto!(r);
 
+        // Seakeeping {{{5
+        if !self.is_submarine() {
+            self.report_seakeeping(&mut r);
+        }
+
+        // Custom Notes {{{5
+        for s in self.notes.iter() {
+            addto!(r, "{}", s);
+        }
+
+        r.join("\n")
+    }
+
+    // report_armour {{{4
+    /// Armour section of the report.
+    ///
+    fn report_armour(&self, r: &mut Vec<String>) {
+        addto!(r);
+        addto!(r, "Armour:");
+
+        if self.armor.main.thick + self.armor.end.thick + self.armor.upper.thick + self.armor.bulkhead.thick > 0.0 {
+            addto!(r, " - Belts:    Width (max)    Length (avg)    Height (avg)");
+            if self.armor.main.thick > 0.0 {
+                addto!(r, "    Main:    {}\" / {:.0} mm    {:.2} ft / {:.2} m    {:.2} ft / {:.2} m",
+                    num!(self.armor.main.thick, if self.armor.main.thick < 10.0 { 2 } else { 1 }),
+                    metric(self.armor.main.thick, LengthSmall, self.armor.units),
+                    self.armor.main.len,
+                    metric(self.armor.main.len, LengthLong, self.armor.units),
+                    self.armor.main.hgt,
+                    metric(self.armor.main.hgt, LengthLong, self.armor.units),
+                );
+            }
+
+            if self.armor.end.thick > 0.0 {
+                addto!(r, "    Ends:    {}\" / {:.0} mm    {:.2} ft / {:.2} m    {:.2} ft / {:.2} m",
+                    num!(self.armor.end.thick, if self.armor.end.thick < 10.0 { 2 } else { 1 }),
+                    metric(self.armor.end.thick, LengthSmall, self.armor.units),
+                    self.armor.end.len,
+                    metric(self.armor.end.len, LengthLong, self.armor.units),
+                    self.armor.end.hgt,
+                    metric(self.armor.end.hgt, LengthLong, self.armor.units),
+                );
+                if self.armor.main.len + self.armor.end.len < self.hull.lwl() {
+                    addto!(r, "    {:.2} ft / {:.2} m Unarmoured ends",
+                        self.hull.lwl() - self.armor.main.len - self.armor.end.len,
+                        metric(self.hull.lwl() - self.armor.main.len - self.armor.end.len, LengthLong, self.armor.units)
+                    );
+                }
+            } else if self.armor.main.len < self.hull.lwl() {
+                addto!(r, "    Ends:    Unarmoured");
+            }
+
+            if self.armor.upper.thick > 0.0 {
+                addto!(r, "    Upper:    {}\" / {:.0} mm    {:.2} ft / {:.2} m    {:.2} ft / {:.2} m",
+                    num!(self.armor.upper.thick, if self.armor.upper.thick < 10.0 { 2 } else { 1 }),
+                    metric(self.armor.upper.thick, LengthSmall, self.armor.units),
+                    self.armor.upper.len,
+                    metric(self.armor.upper.len, LengthLong, self.armor.units),
+                    self.armor.upper.hgt,
+                    metric(self.armor.upper.hgt, LengthLong, self.armor.units),
+                );
+            }
+
+            if self.armor.main.thick > 0.0 {
+                addto!(r, "    Main Belt covers {:.0} % of normal length",
+                    self.armor.belt_coverage(self.hull.lwl())*100.0
+                );
+                if self.armor.belt_coverage(self.hull.lwl()) < self.hull_room() {
+                    addto!(r, "    Main belt does not fully cover magazines and engineering spaces");
+                }
+            }
+
+            if self.armor.incline != 0.0 {
+                addto!(r, "    Main Belt inclined {:.2} degrees (positive = in)",
+                    self.armor.incline
+                );
+            }
+
+            if self.armor.bulkhead.thick > 0.0 {
+                addto!(r);
+                addto!(r, "- Torpedo Bulkhead - {} bulkheads:",
+                    match self.armor.bh_kind {
+                        BulkheadType::Strengthened => "Strengthened structural",
+                        BulkheadType::Additional   => "Additional damage containing",
+                    }
+                );
+                addto!(r, "        {}\" / {:.0} mm    {:.2} ft / {:.2} m    {:.2} ft / {:.2} m",
+                    num!(self.armor.bulkhead.thick, if self.armor.bulkhead.thick < 10.0 { 2 } else { 1 }),
+                    metric(self.armor.bulkhead.thick, LengthSmall, self.armor.units),
+                    self.armor.bulkhead.len,
+                    metric(self.armor.bulkhead.len, LengthLong, self.armor.units),
+                    self.armor.bulkhead.hgt,
+                    metric(self.armor.bulkhead.hgt, LengthLong, self.armor.units),
+                );
+                addto!(r, "    Beam between torpedo bulkheads {:.2} ft / {:.2} m",
+                    self.armor.bh_beam,
+                    metric(self.armor.bh_beam, LengthLong, self.armor.units)
+                );
+                addto!(r);
+            }
+
+            if self.armor.bulge.thick > 0.0 || self.wgts.void > 0 {
+                addto!(r, "- Hull {}:",
+                    if self.hull.b == self.hull.bb { "void" }
+                    else { "Bulges" }
+                );
+                addto!(r, "        {}\" / {:.0} mm    {:.2} ft / {:.2} m    {:.2} ft / {:.2} m",
+                    num!(self.armor.bulge.thick, if self.armor.bulge.thick < 10.0 { 2 } else { 1 }),
+                    metric(self.armor.bulge.thick, LengthSmall, self.armor.units),
+                    self.armor.bulge.len,
+                    metric(self.armor.bulge.len, LengthLong, self.armor.units),
+                    self.armor.bulge.hgt,
+                    metric(self.armor.bulge.hgt, LengthLong, self.armor.units),
+                );
+            addto!(r);
+            }
+
+            if self.tds_charge() > 0.0 {
+                addto!(r, "- Torpedo protection withstands {} lbs / {} kg charge each side, {:.0}% of vitals covered",
+                    num!(self.tds_charge(), 0),
+                    num!(metric(self.tds_charge(), Weight, self.armor.units), 0),
+                    self.armor.tds_coverage(self.hull.lwl()) * 100.0
+                );
+                addto!(r);
+            }
+        }
+
+        if self.wgt_gun_armor() > 0.0 {
+            addto!(r, "- Gun armour:    Face (max)    Other gunhouse (avg)    Barbette/hoist (max)");
+
+            for (i, b) in self.batteries.iter().enumerate() {
+                if b.armor_face == 0.0 &&
+                b.armor_back == 0.0 &&
+                b.armor_barb == 0.0 { continue; }
+                addto!(r, "    {}:    {}        {}            {}",
+                    ordinal(i),
+                    if b.armor_face == 0.0 { "-".into() } else { format!("{}\" / {:.0} mm", num!(b.armor_face, if b.armor_face >= 10.0 { 1 } else { 2 }), metric(b.armor_face, LengthSmall, b.units)) },
+                    if b.armor_back == 0.0 { "-".into() } else { format!("{}\" / {:.0} mm", num!(b.armor_back, if b.armor_back >= 10.0 { 1 } else { 2 }), metric(b.armor_back, LengthSmall, b.units)) },
+                    if b.armor_barb == 0.0 { "-".into() } else { format!("{}\" / {:.0} mm", num!(b.armor_barb, if b.armor_barb >= 10.0 { 1 } else { 2 }), metric(b.armor_barb, LengthSmall, b.units)) },
+                );
+            }
+            addto!(r);
+        }
+
+        if self.armor.deck.fc + self.armor.deck.md + self.armor.deck.qd > 0.0 {
+            addto!(r, "- {}:",
+                self.armor.deck.kind
+            );
+            // TODO: Change spelling to Fore (required to match Springsharp reports)
+            addto!(r, "    For and Aft decks: {:.2}\" / {:.0} mm",
+                self.armor.deck.md,
+                metric(self.armor.deck.md, LengthSmall, self.armor.units)
+            );
+            // TODO: Change spelling to Quarterdeck (required to match Springsharp reports)
+            addto!(r, "    Forecastle: {:.2}\" / {:.0} mm    Quarter deck: {:.2}\" / {:.0} mm",
+                self.armor.deck.fc,
+                metric(self.armor.deck.fc, LengthSmall, self.armor.units),
+                self.armor.deck.qd,
+                metric(self.armor.deck.qd, LengthSmall, self.armor.units)
+            );
+            addto!(r);
+        }
+
+        if self.armor.ct_fwd.thick + self.armor.ct_aft.thick > 0.0 {
+            // TODO: Remove stray space before comma (required to match Springsharp reports)
+            addto!(r, "- Conning towers: Forward {:.2}\" / {:.0} mm, Aft {:.2}\" / {:.0} mm",
+                self.armor.ct_fwd.thick,
+                metric(self.armor.ct_fwd.thick, LengthSmall, self.armor.units),
+                self.armor.ct_aft.thick,
+                metric(self.armor.ct_aft.thick, LengthSmall, self.armor.units)
+            );
+            addto!(r);
+        }
+    }
+
+    // report_seakeeping {{{4
+    /// Stability, seakeeping and strength sections of the report, which
+    /// only apply to surface ships.
+    ///
+    fn report_seakeeping(&self, r: &mut Vec<String>) {
         addto!(r, "Overall survivability and seakeeping ability:"); // {{{5
         addto!(r, "    Survivability (Non-critical penetrating hits needed to sink ship):");
         addto!(r, "    {:.0} lbs / {:.0} Kg = {:.1} x {:.1} \" / {:.0} mm shells or {:.1} torpedoes",
@@ -1909,6 +3068,15 @@ impl Ship { // {{{3
             self.metacenter(),
             metric(self.metacenter(), LengthLong, Imperial)
         );
+        addto!(r, "        - From weights: KM {:.1} ft - KG {:.1} ft = GM {:.1} ft / {:.1} m",
+            self.hull.km(),
+            self.kg(),
+            self.gm(),
+            metric(self.gm(), LengthLong, Imperial)
+        );
+        addto!(r, "    Trim at normal load: {}",
+            Self::trim_desc(self.trim(), self.lcg())
+        );
         addto!(r, "    Roll period: {:.1} seconds",
             self.roll_period()
         );
@@ -1923,6 +3091,57 @@ impl Ship { // {{{3
         );
         addto!(r);
 
+        addto!(r, "Survivability and seakeeping by loading condition:"); // {{{5
+        addto!(r, "    {:<28} {:>8} {:>7} {:>9} {:>8} {:>6} {:>6} {:>7} {:>6} {:>6}",
+            "Condition", "Disp (t)", "T (ft)", "Stability", "GM (ft)", "Roll", "Steady", "Seaboat", "Shells", "Torps");
+        for load in self.loads_all() {
+            let (d, t) = (self.d_load(&load), self.t_load(&load));
+            let capsize = self.capsize_warn_calc(d, t);
+
+            // A ship that capsizes has no roll period, steadiness or seakeeping
+            let (roll, steady, seaboat) = if capsize {
+                ("-".to_string(), "-".to_string(), "-".to_string())
+            } else {
+                (
+                    format!("{:.1}", self.roll_period_calc(d, t)),
+                    format!("{:.0}%", self.steadiness_calc(d, t)),
+                    format!("{:.2}", self.seakeeping_calc(d, t)),
+                )
+            };
+
+            addto!(r, "    {:<28} {:>8} {:>7.2} {:>9.2} {:>8.2} {:>6} {:>6} {:>7} {:>6.1} {:>6.1}{}",
+                load.to_string(),
+                num!(d, 0),
+                t,
+                self.stability_adj_calc(d, t),
+                self.metacenter_calc(d, t),
+                roll,
+                steady,
+                seaboat,
+                self.damage_shell_num_calc(d, t),
+                self.damage_torp_num_calc(d, t),
+                addif!(capsize, " Capsizes")
+            );
+        }
+        addto!(r);
+
+        addto!(r, "Righting arm by loading condition:"); // {{{5
+        addto!(r, "    {:<28} {:>8} {:>8} {:>13} {:>13}",
+            "Condition", "KG (ft)", "GM (ft)", "Max GZ (ft)", "Range (deg)");
+        for curve in self.gz_curves() {
+            let max = curve.max();
+
+            addto!(r, "    {:<28} {:>8.2} {:>8.2} {:>13} {:>13.1}{}",
+                curve.label,
+                curve.kg,
+                curve.gm,
+                format!("{:.2} at {:.0}", max.gz, max.heel),
+                curve.range(),
+                addif!(curve.gm <= 0.0, " Unstable")
+            );
+        }
+        addto!(r);
+
         addto!(r, "Hull form characteristics:"); // {{{5
         addto!(r, "    Hull has {},",
             self.hull.freeboard_desc()
@@ -2007,6 +3226,20 @@ impl Ship { // {{{3
         addto!(r, "        - Overall: {:.2}",
             self.str_comp()
         );
+        addto!(r, "Hull girder (section modulus {} sq in-ft):",
+            num!(self.section_modulus(), 0)
+        );
+        for wave in Wave::ALL {
+            let strength = self.strength(wave);
+
+            addto!(r, "        - {}: {} ft-tons {}, {:.1} tons/sq in, shear {} tons",
+                wave,
+                num!(strength.max_moment().abs(), 0),
+                if strength.max_moment() >= 0.0 { "hogging" } else { "sagging" },
+                self.hull_stress(wave),
+                num!(strength.max_shear(), 0)
+            );
+        }
 
         if self.tender_warn() && !self.capsize_warn() {
             addto!(r, "Caution: Poor stability - excessive risk of capsizing");
@@ -2014,6 +3247,18 @@ impl Ship { // {{{3
         if self.hull_strained() {
             addto!(r, "Caution: Hull subject to strain in open-sea");
         }
+        if self.hull_overstressed() {
+            addto!(r, "Caution: Hull girder overstressed on a wave as long as the ship");
+        }
+        if self.trim_warn() {
+            if self.trim() > 0.0 && self.hull.is_wet_fwd() {
+                addto!(r, "Caution: Trimmed by the head with a low bow - very wet forward");
+            } else {
+                addto!(r, "Caution: Trimmed by the {} - ballast needed",
+                    if self.trim() > 0.0 { "head" } else { "stern" }
+                );
+            }
+        }
         addto!(r, "    {} machinery, storage, compartmentation space",
             self.hull_room_quality()
         );
@@ -2026,45 +3271,489 @@ impl Ship { // {{{3
         }
 
         addto!(r);
+    }
+}
 
-        // Custom Notes {{{5
-        for s in self.notes.iter() {
-            addto!(r, "{}", s);
+// Ship Report {{{2
+impl Ship { // {{{3
+    // ship_report {{{4
+    /// Key figures of the report as data.
+    ///
+    pub fn ship_report(&self) -> ShipReport {
+        let mounts = Mount::place(self);
+
+        ShipReport {
+            name: self.name.clone(),
+            country: self.country.clone(),
+            kind: self.kind.clone(),
+            year: self.year,
+            d_std: self.d_std(),
+            d: self.hull.d(),
+            d_max: self.d_max(),
+            vmax: self.engine.vmax,
+            batteries: self.batteries.iter().enumerate()
+                .filter(|(_, b)| b.num > 0)
+                .map(|(i, b)| BatteryReport {
+                    num: b.num,
+                    diam: b.diam,
+                    kind: b.kind.to_string(),
+                    shell_wgt: b.shell_wgt(),
+                    rof: b.rof(),
+                    shells_min: b.shells_min(),
+                    wgt_min: b.wgt_min(),
+                    max_elevation: b.max_elevation,
+                    max_range: b.max_range(),
+                    magazine: b.mag_desc(),
+                    bearing: Bearing::of(&mounts, i),
+                })
+                .collect(),
+            torps: self.torps.iter()
+                .filter(|t| t.num > 0)
+                .map(|t| TorpedoReport {
+                    num: t.num,
+                    diam: t.diam,
+                    len: t.len,
+                    year: t.year,
+                    warhead: t.warhead(),
+                    settings: t.settings(),
+                    hits: self.damage_torp_hits(t),
+                })
+                .collect(),
+            tds_charge: self.tds_charge(),
+            wgt_broad: self.wgt_broad(),
+            shells_min: self.shells_min(),
+            wgt_min: self.wgt_min(),
+            aa_long: self.aa_score(AABand::Long),
+            aa_medium: self.aa_score(AABand::Medium),
+            aa_short: self.aa_score(AABand::Short),
+        }
+    }
+}
+
+// Hydrostatics Table {{{2
+impl Ship { // {{{3
+    // hydrostatics_table {{{4
+    /// Print hydrostatics().
+    ///
+    pub fn hydrostatics_table(&self) -> String {
+        let mut r: Vec<String> = Vec::new();
+
+        addto!(r, "{}: hydrostatics from light to maximum displacement", self.name);
+        addto!(r);
+        addto!(r, "{:<28} {:>12} {:>10} {:>6} {:>14} {:>14} {:>10}",
+            "Condition", "Displacement", "Draught", "Cb", "Waterplane", "Metacentric", "Stability");
+        addto!(r, "{:<28} {:>12} {:>10} {:>6} {:>14} {:>14}",
+            "", "(t)", "(ft)", "", "area (ft²)", "height (ft)");
+
+        for h in self.hydrostatics() {
+            addto!(r, "{:<28} {:>12} {:>10.2} {:>6.2} {:>14} {:>14.2} {:>10.2}{}",
+                h.label,
+                num!(h.d, 0),
+                h.t,
+                h.cb,
+                num!(h.wp, 0),
+                h.metacenter,
+                h.stability,
+                addif!(h.stability < 1.0, " Unstable")
+            );
         }
 
         r.join("\n")
     }
 }
 
-// Inernals Output {{{2
-#[cfg(debug_assertions)]
-impl Ship {
-    // Print internal values {{{3
-    pub fn internals(&self) -> String {
-        let mut s: Vec<String> = Vec::new();
+// Speed Curves {{{2
+// nice_max {{{3
+/// Round a maximum value up to a round multiple of a power of ten so it can
+/// be used as the top of a chart axis divided into CURVE_TICKS.
+///
+fn nice_max(max: f64) -> f64 {
+    if max <= 0.0 { return 1.0; }
 
-        s.push("Internal values".to_string());
-        s.push("===============".to_string());
-        s.push("".to_string());
-        s.push("Gun Batteries".to_string());
-        s.push("------------".to_string());
-        s.push(format!("wgt_guns = {}", self.wgt_guns()));
-        s.push(format!("wgt_gun_mounts = {}", self.wgt_gun_mounts()));
-        s.push(format!("wgt_mag = {}", self.wgt_mag()));
-        s.push(format!("wgt_gun_armor = {}", self.wgt_gun_armor()));
-        s.push(format!("wgt_borne = {}", self.wgt_borne()));
-        s.push(format!("super_factor = {}", self.gun_super_factor()));
-        s.push(format!("gun_wtf = {}", self.gun_wtf()));
-        s.push("".to_string());
+    let mag = 10_f64.powf(max.log10().floor());
+    for m in [1.0, 1.5, 2.0, 2.5, 3.0, 4.0, 5.0, 6.0, 8.0, 10.0] {
+        if m * mag >= max { return m * mag; }
+    }
+    10.0 * mag
+}
 
-        for (i, b) in self.batteries.iter().enumerate() {
-            s.push(format!("battery[{}]", i));
-            s.push("-----------".to_string());
-            b.internals(self.hull.clone(), self.wgt_broad());
-            s.push("".to_string());
+impl Ship { // {{{3
+    /// Lowest speed of the speed curves (kts). Range grows without bound as
+    /// speed falls so slower speeds swamp the rest of the curve.
+    const CURVE_MIN: f64 = 5.0;
+
+    /// Speed interval between points of the speed curves (kts).
+    const CURVE_STEP: f64 = 1.0;
+
+    /// Number of divisions of each axis of the speed curve chart.
+    pub const CURVE_TICKS: u32 = 5;
+
+    /// Width and height of a chart (px).
+    const CHART_SIZE: (f64, f64) = (600.0, 400.0);
+
+    /// Left, top, width and height of the plot area of a chart (px).
+    const CHART_BOX: (f64, f64, f64, f64) = (80.0, 40.0, 440.0, 300.0);
+
+    // curves {{{4
+    /// Speed-power and range-speed curves from CURVE_MIN up to maximum speed.
+    ///
+    pub fn curves(&self) -> Vec<SpeedPoint> {
+        let mut speeds = Vec::new();
+
+        let mut v = Self::CURVE_MIN;
+        while v < self.engine.vmax {
+            speeds.push(v);
+            v += Self::CURVE_STEP;
         }
+        if self.engine.vmax > 0.0 { speeds.push(self.engine.vmax); }
 
-        s.push(format!("Cs = {}", self.hull.cs()));
+        self.engine.curves(
+            &speeds,
+            self.hull.d(),
+            self.hull.lwl(),
+            self.hull.leff(),
+            self.hull.cs(),
+            self.hull.ws()
+        )
+    }
+
+    // curves_csv {{{4
+    /// Speed curves as CSV with a header line.
+    ///
+    pub fn curves_csv(&self) -> String {
+        let mut s: Vec<String> = Vec::new();
+
+        s.push("speed_kts,shp,rf,rw,wave_pct,range_nm".into());
+        for p in self.curves() {
+            let wave = if p.rf + p.rw > 0.0 { p.rw / (p.rf + p.rw) * 100.0 } else { 0.0 };
+
+            s.push(format!("{:.1},{:.0},{:.2},{:.2},{:.1},{:.0}",
+                p.speed, p.hp, p.rf, p.rw, wave, p.range));
+        }
+
+        s.join("\n")
+    }
+
+    // curves_scale {{{4
+    /// Axis maxima for charting the speed curves: speed, shaft horsepower
+    /// and range.
+    ///
+    pub fn curves_scale(&self) -> (f64, f64, f64) {
+        let curves = self.curves();
+
+        (
+            nice_max(self.engine.vmax),
+            nice_max(curves.iter().map(|p| p.hp).fold(0.0, f64::max)),
+            nice_max(curves.iter().map(|p| p.range).fold(0.0, f64::max)),
+        )
+    }
+
+    // curves_path {{{4
+    /// SVG path data for one speed curve drawn in a box of `w` by `h` with
+    /// its top left corner at `x`, `y`. `value` selects the plotted value
+    /// and is scaled so that `ymax` is at the top of the box.
+    ///
+    pub fn curves_path(&self, value: fn(&SpeedPoint) -> f64, ymax: f64, x: f64, y: f64, w: f64, h: f64) -> String {
+        let (xmax, _, _) = self.curves_scale();
+
+        self.curves().iter().enumerate()
+            .map(|(i, p)| format!("{} {:.2} {:.2}",
+                if i == 0 { "M" } else { "L" },
+                x + p.speed / xmax * w,
+                y + h - value(p) / ymax * h,
+            ))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+
+    // curves_svg {{{4
+    /// Speed curves as an SVG document: shaft horsepower against the left
+    /// axis and range at full bunkerage against the right axis.
+    ///
+    pub fn curves_svg(&self) -> String {
+        let (x, y, w, h) = Self::CHART_BOX;
+        let (vmax, hpmax, rangemax) = self.curves_scale();
+
+        let y_ticks = |f: f64, gy: f64| vec![
+            format!(r##"<text x="{}" y="{}" text-anchor="end" fill="#c0392b">{}</text>"##, x - 6.0, gy + 4.0, num!(f * hpmax, 0)),
+            format!(r##"<text x="{}" y="{}" fill="#2980b9">{}</text>"##, x + w + 6.0, gy + 4.0, num!(f * rangemax, 0)),
+        ];
+        let y_labels = [
+            format!(r##"<text x="{}" y="{}" text-anchor="middle" fill="#c0392b">shp</text>"##, x - 40.0, y - 10.0),
+            format!(r##"<text x="{}" y="{}" text-anchor="middle" fill="#2980b9">Range (nm)</text>"##, x + w + 30.0, y - 10.0),
+        ];
+
+        let mut s: Vec<String> = Vec::new();
+
+        // Cruising speed
+        if self.engine.vcruise > 0.0 && self.engine.vcruise <= vmax {
+            let cx = x + self.engine.vcruise / vmax * w;
+            s.push(format!(r##"<line x1="{cx}" y1="{y}" x2="{cx}" y2="{}" stroke="#7f8c8d" stroke-dasharray="4 4"/>"##, y + h));
+        }
+
+        // Curves
+        s.push(format!(r##"<path d="{}" fill="none" stroke="#c0392b" stroke-width="2"/>"##,
+            self.curves_path(|p| p.hp, hpmax, x, y, w, h)));
+        s.push(format!(r##"<path d="{}" fill="none" stroke="#2980b9" stroke-width="2"/>"##,
+            self.curves_path(|p| p.range, rangemax, x, y, w, h)));
+
+        self.chart_svg("Speed (kts)", vmax, &y_ticks, &y_labels, s)
+    }
+
+    // chart_svg {{{4
+    /// SVG document of a chart: the ship's name as a title, a grid of
+    /// CURVE_TICKS divisions, the bottom axis up to `x_max` and a border
+    /// around the plot area of CHART_BOX, with the given body drawn inside.
+    ///
+    /// `y_ticks` gives the labels of the side axes at each division from its
+    /// fraction of the height and its position, and `y_labels` the titles of
+    /// the side axes.
+    ///
+    fn chart_svg(&self, x_label: &str, x_max: f64, y_ticks: &dyn Fn(f64, f64) -> Vec<String>, y_labels: &[String], body: Vec<String>) -> String {
+        let (width, height) = Self::CHART_SIZE;
+        let (x, y, w, h) = Self::CHART_BOX;
+        let ticks = Self::CURVE_TICKS;
+
+        let mut s: Vec<String> = Vec::new();
+
+        s.push(format!(r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" font-family="sans-serif" font-size="12">"#, width, height));
+        s.push(format!(r#"<rect width="{}" height="{}" fill="white"/>"#, width, height));
+        s.push(format!(r#"<text x="{}" y="20" text-anchor="middle" font-weight="bold">{}</text>"#, width / 2.0,
+            self.name.replace('&', "&amp;").replace('<', "&lt;")));
+
+        // Grid and axis labels
+        for i in 0..=ticks {
+            let f = i as f64 / ticks as f64;
+            let (gx, gy) = (x + f * w, y + h - f * h);
+
+            s.push(format!(r##"<line x1="{gx}" y1="{y}" x2="{gx}" y2="{}" stroke="#ddd"/>"##, y + h));
+            s.push(format!(r##"<line x1="{x}" y1="{gy}" x2="{}" y2="{gy}" stroke="#ddd"/>"##, x + w));
+
+            s.push(format!(r#"<text x="{gx}" y="{}" text-anchor="middle">{}</text>"#, y + h + 16.0, num!(f * x_max, 0)));
+            s.extend(y_ticks(f, gy));
+        }
+        s.push(format!(r#"<text x="{}" y="{}" text-anchor="middle">{}</text>"#, x + w / 2.0, y + h + 40.0, x_label));
+        s.extend(y_labels.iter().cloned());
+
+        s.extend(body);
+
+        s.push(format!(r#"<rect x="{x}" y="{y}" width="{w}" height="{h}" fill="none" stroke="black"/>"#));
+        s.push("</svg>".into());
+
+        s.join("\n")
+    }
+}
+
+// Righting Arm Curves {{{2
+impl Ship {
+    /// Colours of the righting arm curves of each loading condition.
+    const GZ_COLORS: [&str; 6] = ["#c0392b", "#2980b9", "#27ae60", "#8e44ad", "#e67e22", "#7f8c8d"];
+
+    // gz_curve {{{3
+    /// Righting arm curve in a given loading condition.
+    ///
+    /// The hull is treated as a box of the waterline beam up to the height of
+    /// the deck amidships, so the deck edge goes under at the same angle.
+    ///
+    pub fn gz_curve(&self, load: &LoadType) -> GzCurve {
+        let (d, t) = (self.d_load(load), self.t_load(load));
+
+        let section = Section {
+            beam: self.hull.bb,
+            depth: self.hull.t + self.hull.freeboard_dist(),
+            draft: t,
+        };
+
+        GzCurve::new(load.to_string(), &section, self.hull.km_calc(d, t), self.kg_load(load))
+    }
+
+    // gz_curves {{{3
+    /// Righting arm curves of every loading condition in loads_all().
+    ///
+    pub fn gz_curves(&self) -> Vec<GzCurve> {
+        self.loads_all().iter().map(|load| self.gz_curve(load)).collect()
+    }
+
+    // gz_csv {{{3
+    /// Righting arm curves as CSV with a header line.
+    ///
+    pub fn gz_csv(&self) -> String {
+        let mut s: Vec<String> = Vec::new();
+
+        s.push("condition,heel_deg,gz_ft".into());
+        for curve in self.gz_curves() {
+            for p in curve.points.iter() {
+                s.push(format!("\"{}\",{:.1},{:.3}", curve.label, p.heel, p.gz));
+            }
+        }
+
+        s.join("\n")
+    }
+
+    // gz_svg {{{3
+    /// Righting arm curves of every loading condition as an SVG document.
+    ///
+    pub fn gz_svg(&self) -> String {
+        let (x, y, w, h) = Self::CHART_BOX;
+
+        let curves = self.gz_curves();
+        let heel_max = nice_max(curves.iter().map(|c| c.range()).fold(0.0, f64::max));
+        let gz_max = nice_max(curves.iter().map(|c| c.max().gz).fold(0.0, f64::max));
+
+        let y_ticks = |f: f64, gy: f64| vec![
+            format!(r#"<text x="{}" y="{}" text-anchor="end">{:.2}</text>"#, x - 6.0, gy + 4.0, f * gz_max),
+        ];
+        let y_labels = [
+            format!(r#"<text x="{}" y="{}" text-anchor="middle">GZ (ft)</text>"#, x - 40.0, y - 10.0),
+        ];
+
+        let mut s: Vec<String> = Vec::new();
+
+        // Curves, with a key in the top right corner
+        for (i, curve) in curves.iter().enumerate() {
+            let color = Self::GZ_COLORS[i % Self::GZ_COLORS.len()];
+            let path = curve.points.iter().enumerate()
+                .map(|(j, p)| format!("{} {:.2} {:.2}",
+                    if j == 0 { "M" } else { "L" },
+                    x + p.heel / heel_max * w,
+                    y + h - p.gz / gz_max * h,
+                ))
+                .collect::<Vec<String>>()
+                .join(" ");
+
+            s.push(format!(r#"<path d="{}" fill="none" stroke="{}" stroke-width="2"/>"#, path, color));
+            s.push(format!(r#"<text x="{}" y="{}" text-anchor="end" fill="{}">{}</text>"#,
+                x + w - 6.0, y + 16.0 * (i + 1) as f64, color, curve.label));
+        }
+
+        self.chart_svg("Heel (deg)", heel_max, &y_ticks, &y_labels, s)
+    }
+}
+
+// Longitudinal Strength {{{2
+impl Ship {
+    /// Height of the standard wave from trough to crest as a fraction of
+    /// waterline length.
+    const WAVE_HGT: f64 = 1.0 / 20.0;
+
+    /// Weight of steel plate one inch thick (lbs/sq ft).
+    const PLATE_WGT: f64 = 40.8;
+
+    /// Fraction of the hull structure that runs the length of the ship.
+    const STRUCT_LONG: f64 = 0.5;
+
+    /// Largest stress in a seaway before the hull is overstrained
+    /// (tons/sq in).
+    const STRESS_MAX: f64 = 9.0;
+
+    // girder {{{3
+    /// Hull loaded by the weights of kg_dist() placed along its length.
+    ///
+    /// Each battery is split into the mounts placed by Mount::place().
+    ///
+    pub fn girder(&self) -> Girder {
+        let lwl = self.hull.lwl();
+
+        let mounts = Mount::place(self);
+        let guns = |i: usize| mounts.iter().filter(|m| m.battery == i).map(|m| m.guns).sum::<u32>();
+
+        // Batteries are split between their mounts, or carried whole where
+        // none of their mounts could be placed
+        let (btrys, dist) = self.centres_split();
+        let mut weights: Vec<(f64, f64, f64)> = Vec::new();
+        for (i, (_, w, x, _, len)) in btrys {
+            if guns(i) == 0 {
+                weights.push((w, x, len));
+                continue;
+            }
+
+            weights.extend(mounts.iter()
+                .filter(|m| m.battery == i)
+                .map(|m| (w * m.guns as f64 / guns(i) as f64, lwl / 2.0 - m.x, m.radius * 2.0))
+            );
+        }
+        weights.extend(dist.into_iter().map(|(_, w, x, _, len)| (w, x, len)));
+
+        Girder {
+            lwl,
+            cp: Hull::cp(self.hull.cb()),
+            cwp: self.hull.cwp(),
+            bb: self.hull.bb,
+            weights,
+        }
+    }
+
+    // strength {{{3
+    /// Shear force and bending moment in still water or on a standard wave
+    /// as long as the ship.
+    ///
+    pub fn strength(&self, wave: Wave) -> Strength {
+        self.girder().strength(wave, self.hull.lwl() * Self::WAVE_HGT)
+    }
+
+    // section_modulus {{{3
+    /// Section modulus of the hull girder amidships (sq in-ft).
+    ///
+    /// The structure of wgt_struct() is taken as plating of an even
+    /// thickness, part of which forms a box girder of the beam and the depth
+    /// of the hull.
+    ///
+    pub fn section_modulus(&self) -> f64 {
+        let plate = self.wgt_struct() / Self::PLATE_WGT * Self::STRUCT_LONG; // in
+        let depth = self.hull.t + self.hull.freeboard_dist();
+
+        // Deck and bottom at half depth from the axis, and two sides
+        12.0 * plate * (self.hull.bb * depth + depth.powf(2.0) / 3.0)
+    }
+
+    // hull_stress {{{3
+    /// Largest stress in the hull girder in still water or on a standard
+    /// wave (tons/sq in).
+    ///
+    pub fn hull_stress(&self, wave: Wave) -> f64 {
+        let z = self.section_modulus();
+        if z <= 0.0 { return 0.0; } // Catch divide by zero
+
+        self.strength(wave).max_moment().abs() / z
+    }
+
+    // hull_overstressed {{{3
+    /// Is the hull overstrained on a standard wave?
+    ///
+    pub fn hull_overstressed(&self) -> bool {
+        Wave::ALL.iter().any(|wave| self.hull_stress(*wave) > Self::STRESS_MAX)
+    }
+}
+
+// Inernals Output {{{2
+#[cfg(debug_assertions)]
+impl Ship {
+    // Print internal values {{{3
+    pub fn internals(&self) -> String {
+        let mut s: Vec<String> = Vec::new();
+
+        s.push("Internal values".to_string());
+        s.push("===============".to_string());
+        s.push("".to_string());
+        s.push("Gun Batteries".to_string());
+        s.push("------------".to_string());
+        s.push(format!("wgt_guns = {}", self.wgt_guns()));
+        s.push(format!("wgt_gun_mounts = {}", self.wgt_gun_mounts()));
+        s.push(format!("wgt_mag = {}", self.wgt_mag()));
+        s.push(format!("wgt_gun_armor = {}", self.wgt_gun_armor()));
+        s.push(format!("wgt_borne = {}", self.wgt_borne()));
+        s.push(format!("super_factor = {}", self.gun_super_factor()));
+        s.push(format!("gun_wtf = {}", self.gun_wtf()));
+        s.push("".to_string());
+
+        for (i, b) in self.batteries.iter().enumerate() {
+            s.push(format!("battery[{}]", i));
+            s.push("-----------".to_string());
+            b.internals(self.hull.clone(), self.wgt_broad());
+            s.push("".to_string());
+        }
+
+        s.push(format!("Cs = {}", self.hull.cs()));
         s.push(format!("Cm = {}", Hull::cm(self.hull.cb())));
         s.push(format!("Cp = {}", Hull::cp(self.hull.cb())));
         s.push(format!("Cwp = {}", self.hull.cwp()));
@@ -2137,7 +3826,7 @@ mod ship {
     use super::*;
     use crate::test_support::*;
     use crate::hull::SternType;
-    use crate::weapons::TorpedoMountType;
+    use crate::weapons::{TorpedoMountType, GunType, GunLayoutType};
 
     fn get_hull() -> Hull {
 
@@ -2206,6 +3895,7 @@ mod ship {
                     let mut ship = Ship::default();
                     ship.hull = get_hull().clone();
 
+                    ship.add_torps();
                     ship.torps[0].year = 1920;
                     ship.torps[0].num = 3;
                     ship.torps[0].mounts = 2;
@@ -2213,8 +3903,6 @@ mod ship {
                     ship.torps[0].len = 10.0;
                     ship.torps[0].mount_kind = kind;
 
-                    ship.torps[1].num = 0;
-
                     assert_eq!(expected, to_place(ship.deck_space(), 4));
                 }
             )*
@@ -2245,6 +3933,7 @@ mod ship {
                     let mut ship = Ship::default();
                     ship.hull = get_hull().clone();
 
+                    ship.add_torps();
                     ship.torps[0].year = 1920;
                     ship.torps[0].num = 3;
                     ship.torps[0].mounts = 2;
@@ -2252,8 +3941,6 @@ mod ship {
                     ship.torps[0].len = 10.0;
                     ship.torps[0].mount_kind = kind;
 
-                    ship.torps[1].num = 0;
-
                     assert_eq!(expected, to_place(ship.hull_space(), 4));
                 }
             )*
@@ -2319,6 +4006,1241 @@ mod ship {
         crew_min_d_eq_zero: (0, 0.0),
         crew_min_d_eq_1000: (88, 1000.0),
     }
+
+    // Test wgt_dist {{{3
+    macro_rules! test_wgt_dist {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (guns, belt) = $value;
+
+                    let mut ship = get_ship(1920, get_hull(), (guns, 8.0, 45.0, guns));
+                    ship.engine.vmax = 20.0;
+                    ship.engine.vcruise = 10.0;
+                    ship.engine.range = 5000;
+
+                    ship.armor.main.thick = belt;
+                    ship.armor.main.len = 300.0;
+                    ship.armor.main.hgt = 10.0;
+
+                    let dist = ship.wgt_dist();
+                    let wgt = |dist: &Vec<(String, f64)>, label: &str| {
+                        dist.iter().find(|(l, _)| l == label).unwrap().1
+                    };
+
+                    assert_eq!(15, dist.len());
+                    assert_eq!(to_place(ship.wgt_guns() + ship.wgt_gun_mounts(), 3), to_place(wgt(&dist, "Guns"), 3));
+                    assert_eq!(to_place(ship.wgt_engine(), 3), to_place(wgt(&dist, "Machinery"), 3));
+                    assert_eq!(to_place(ship.wgt_hull(), 3), to_place(wgt(&dist, "Hull, fittings & equipment"), 3));
+
+                    // Belt weight grows with belt thickness
+                    let mut thicker = ship.clone();
+                    thicker.armor.main.thick = belt + 1.0;
+                    assert!(wgt(&thicker.wgt_dist(), "Belts") > wgt(&dist, "Belts"));
+                    assert_eq!(belt == 0.0, wgt(&dist, "Belts") == 0.0);
+                }
+            )*
+        }
+    }
+
+    test_wgt_dist! {
+        // name:             (guns, belt)
+        wgt_dist_unarmed:    (0, 0.0),
+        wgt_dist_guns:       (4, 0.0),
+        wgt_dist_guns_armor: (4, 6.0),
+    }
+
+    // Test nice_max {{{3
+    macro_rules! test_nice_max {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, max) = $value;
+
+                    assert_eq!(expected, nice_max(max));
+                }
+            )*
+        }
+    }
+
+    test_nice_max! {
+        // name:            (expected, max)
+        nice_max_zero:      (1.0, 0.0),
+        nice_max_exact:     (20.0, 20.0),
+        nice_max_two:       (20000.0, 15432.0),
+        nice_max_two_half:  (25.0, 21.0),
+        nice_max_four:      (4000.0, 3100.0),
+        nice_max_eight:     (8.0, 7.5),
+        nice_max_ten:       (10.0, 8.5),
+    }
+
+    // Test curves_csv {{{3
+    macro_rules! test_curves_csv {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (lines, vmax) = $value;
+
+                    let mut ship = get_ship(1920, get_hull(), (0, 0.0, 0.0, 0));
+                    ship.engine.vmax = vmax;
+                    ship.engine.vcruise = 10.0;
+                    ship.engine.range = 5000;
+
+                    let csv = ship.curves_csv();
+
+                    assert_eq!(lines, csv.lines().count());
+                    assert!(csv.lines().all(|l| l.split(',').count() == 6));
+                }
+            )*
+        }
+    }
+
+    test_curves_csv! {
+        // name:            (lines, vmax)
+        curves_csv_stopped: (1, 0.0),
+        curves_csv_20kts:   (17, 20.0),
+        curves_csv_slow:    (2, 4.0),
+    }
+
+    // Test hydrostatics {{{3
+    macro_rules! test_hydrostatics {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let range = $value;
+
+                    let mut ship = get_ship(1920, get_hull(), (4, 8.0, 45.0, 4));
+                    ship.engine.vmax = 20.0;
+                    ship.engine.vcruise = 10.0;
+                    ship.engine.range = range;
+
+                    let hydro = ship.hydrostatics();
+
+                    assert_eq!(Ship::HYDRO_STEPS as usize + 3, hydro.len());
+                    assert_eq!(to_place(ship.d_lite(), 3), to_place(hydro[0].d, 3));
+                    assert_eq!(to_place(ship.d_max(), 3), to_place(hydro.last().unwrap().d, 3));
+                    assert!(hydro.windows(2).all(|w| w[0].d <= w[1].d && w[0].t <= w[1].t));
+
+                    let normal = hydro.iter().find(|h| h.label == "Normal").unwrap();
+                    assert_eq!(to_place(ship.hull.t, 3), to_place(normal.t, 3));
+                    assert_eq!(to_place(ship.hull.cb(), 3), to_place(normal.cb, 3));
+                    assert_eq!(to_place(ship.hull.wp(), 3), to_place(normal.wp, 3));
+                    assert_eq!(to_place(ship.metacenter(), 3), to_place(normal.metacenter, 3));
+                    assert_eq!(to_place(ship.stability_adj(), 3), to_place(normal.stability, 3));
+
+                    let max = hydro.iter().find(|h| h.label == "Full load").unwrap();
+                    assert_eq!(to_place(ship.t_max(), 3), to_place(max.t, 3));
+                    assert_eq!(to_place(ship.cb_max(), 3), to_place(max.cb, 3));
+                }
+            )*
+        }
+    }
+
+    test_hydrostatics! {
+        // name:                (range)
+        hydrostatics_range:     5000,
+        hydrostatics_no_range:  0,
+    }
+
+    // Test report_capsize {{{3
+    macro_rules! test_report_capsize {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (capsizes, above) = $value;
+
+                    let mut ship = get_ship(1920, get_hull(), (4, 8.0, 45.0, 4));
+                    ship.engine.vmax = 20.0;
+                    ship.engine.vcruise = 10.0;
+                    ship.engine.range = 5000;
+                    ship.wgts.above = above;
+
+                    let report = ship.report();
+                    let loads: Vec<&str> = report.lines()
+                        .skip_while(|l| !l.starts_with("Survivability and seakeeping by loading condition"))
+                        .take_while(|l| !l.is_empty())
+                        .collect();
+                    let capsized: Vec<&&str> = loads.iter()
+                        .filter(|l| l.ends_with(" Capsizes"))
+                        .collect();
+
+                    assert_eq!(capsizes, !capsized.is_empty());
+                    assert!(loads.iter().all(|l| !l.contains("NaN")));
+                    assert!(capsized.iter().all(|l| l.contains(" - ")));
+                }
+            )*
+        }
+    }
+
+    test_report_capsize! {
+        // name:                (capsizes, above)
+        report_capsize_none:    (false, 0),
+        report_capsize_top:     (true, 20_000),
+    }
+
+    // Test ship_report {{{3
+    macro_rules! test_ship_report {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (main, secondary) = $value;
+
+                    let mut ship = get_ship(1920, get_hull(), (0, 0.0, 0.0, 0));
+
+                    ship.add_battery();
+                    ship.batteries[0].num = main;
+                    ship.batteries[0].diam = 8.0;
+                    ship.batteries[0].mount_kind = MountType::ClosedBarbette;
+                    ship.add_battery();
+                    ship.batteries[1].num = secondary;
+                    ship.batteries[1].diam = 4.0;
+                    ship.batteries[1].kind = GunType::QuickFiring;
+
+                    let report = ship.ship_report();
+                    let b = &ship.batteries;
+
+                    assert_eq!((main > 0) as usize + (secondary > 0) as usize, report.batteries.len());
+                    assert_eq!(to_place(b[0].shells_min() + b[1].shells_min(), 3), to_place(report.shells_min, 3));
+                    assert_eq!(to_place(b[0].wgt_min() + b[1].wgt_min(), 3), to_place(report.wgt_min, 3));
+                    assert_eq!(to_place(ship.wgt_broad(), 3), to_place(report.wgt_broad, 3));
+                }
+            )*
+        }
+    }
+
+    test_ship_report! {
+        // name:                (main, secondary)
+        ship_report_unarmed:    (0, 0),
+        ship_report_main:       (8, 0),
+        ship_report_both:       (8, 12),
+    }
+
+    // Test aa_score {{{3
+    macro_rules! test_aa_score {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (long, medium, short, kinds) = $value;
+
+                    let mut ship = get_ship(1940, get_hull(), (0, 0.0, 0.0, 0));
+                    for (kind, diam) in kinds {
+                        let b = ship.add_battery();
+                        b.num = 4;
+                        b.diam = diam;
+                        b.kind = kind;
+                        b.year = 1940;
+                        b.max_elevation = 85.0;
+                    }
+
+                    let scores = [AABand::Long, AABand::Medium, AABand::Short].map(|band| ship.aa_score(band) > 0.0);
+                    assert_eq!([long, medium, short], scores);
+
+                    let report = ship.ship_report();
+                    let total: f64 = ship.batteries.iter().map(|b| b.aa_score()).sum();
+                    assert_eq!(to_place(total, 3), to_place(report.aa_long + report.aa_medium + report.aa_short, 3));
+                }
+            )*
+        }
+    }
+
+    test_aa_score! {
+        // name:            (long, medium, short, [(kind, diam)])
+        aa_score_none:      (false, false, false, [(GunType::BreechLoading, 14.0), (GunType::QuickFiring, 1.57)]),
+        aa_score_refit:     (true, true, true, [(GunType::DualPurpose, 5.0), (GunType::RapidFire, 1.57), (GunType::MachineGun, 0.79)]),
+        aa_score_light:     (false, true, true, [(GunType::AntiAir, 1.57), (GunType::MachineGun, 0.5)]),
+    }
+
+    // Test damage_shell_num_type {{{3
+    macro_rules! test_damage_shell_num_type {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (ratio, shell) = $value;
+
+                    let ship = get_ship(1920, get_hull(), (4, 8.0, 45.0, 4));
+
+                    assert_eq!(ratio, to_place(ship.damage_shell_num_type(shell) / ship.damage_shell_num(), 3));
+                }
+            )*
+        }
+    }
+
+    test_damage_shell_num_type! {
+        // name:                    (ratio, shell)
+        damage_shell_num_ap:        (1.0, ShellType::AP),
+        damage_shell_num_he:        (1.067, ShellType::HE),
+        damage_shell_num_shrapnel:  (2.5, ShellType::Shrapnel),
+    }
+
+    // Test damage_torp_hits {{{3
+    macro_rules! test_damage_torp_hits {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (ratio, diam, len) = $value;
+
+                    let mut ship = get_ship(1920, get_hull(), (4, 8.0, 45.0, 4));
+
+                    let torp = ship.add_torps();
+                    torp.year = 1920;
+                    torp.num = 4;
+                    torp.diam = 21.0;
+                    torp.len = 22.0;
+
+                    // The main torpedoes are the ones damage_torp_num() uses
+                    let main = ship.torps[0].clone();
+                    assert_eq!(Some(to_place(ship.damage_torp_num(), 6)), ship.damage_torp_hits(&main).map(|h| to_place(h, 6)));
+                    assert_eq!(1, ship.ship_report().torps.len());
+
+                    let other = Torpedoes { year: 1920, num: 1, diam, len, ..Default::default() };
+                    assert_eq!(ratio, ship.damage_torp_hits(&other).map(|h| to_place(h / ship.damage_torp_num(), 3)));
+                }
+            )*
+        }
+    }
+
+    test_damage_torp_hits! {
+        // name:                    (ratio, diam, len)
+        damage_torp_hits_same:      (Some(1.0), 21.0, 22.0),
+        damage_torp_hits_smaller:   (Some(1.655), 18.0, 17.0),
+        damage_torp_hits_larger:    (Some(0.602), 24.0, 29.0),
+        damage_torp_hits_none:      (None, 0.0, 0.0),
+    }
+
+    // Test damage_torp_no_warhead {{{3
+    #[test]
+    fn damage_torp_no_warhead() {
+        let mut ship = get_ship(1920, get_hull(), (4, 8.0, 45.0, 4));
+
+        // Tubes without a torpedo size fall back on the reference torpedo
+        let torp = ship.add_torps();
+        torp.year = 1920;
+        torp.num = 4;
+
+        assert!(ship.damage_torp_num().is_finite());
+        assert_eq!(
+            to_place(ship.damage_torp_ref_calc(ship.hull.d(), ship.hull.t), 6),
+            to_place(ship.damage_torp_num(), 6)
+        );
+        assert_eq!(None, ship.ship_report().torps[0].hits);
+        assert!(!ship.report().contains("inf"), "{}", ship.report());
+    }
+
+    // Test tds_report {{{3
+    macro_rules! test_tds_report {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, thick, diam, len) = $value;
+
+                    let mut ship = get_ship(1920, get_hull(), (0, 0.0, 0.0, 0));
+                    ship.name = "Target".into();
+                    ship.armor.bulkhead.thick = thick;
+                    ship.armor.bulkhead.len = ship.hull.lwl() * 0.65;
+                    ship.armor.bulkhead.hgt = ship.hull.t;
+                    ship.armor.bh_beam = 26.0;
+
+                    let mut reference = Ship { name: "Reference".into(), ..Default::default() };
+                    let torp = reference.add_torps();
+                    torp.year = 1920;
+                    torp.num = 4;
+                    torp.diam = diam;
+                    torp.len = len;
+
+                    let report = ship.tds_report(&reference);
+                    assert!(report.contains(expected), "{}", report);
+                }
+            )*
+        }
+    }
+
+    test_tds_report! {
+        // name:            (expected, bulkhead, diam, len)
+        tds_report_depth:   ("Withstands a 675 lbs / 306 kg charge on each side", 2.0, 18.0, 17.0),
+        tds_report_held:    ("Main: 18.0\" (1920) torpedoes, 308 lbs / 140 kg warhead - Held", 2.0, 18.0, 17.0),
+        tds_report_flooded: ("warhead - Vitals flooded", 2.0, 24.0, 29.0),
+        tds_report_none:    ("Target: No torpedo protection", 0.0, 18.0, 17.0),
+        tds_report_no_torp: ("Reference: No torpedoes", 2.0, 0.0, 0.0),
+    }
+
+    // Test kg_dist {{{3
+    macro_rules! test_kg_dist {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (above, on, two_mounts_up, misc_above) = $value;
+
+                    let mut ship = get_ship(1920, get_hull(), (2 * (above + on), 12.0, 45.0, above + on));
+                    ship.wgts.above = misc_above;
+
+                    let btry = &mut ship.batteries[0];
+                    btry.groups[0].above = above;
+                    btry.groups[0].on = on;
+                    btry.groups[0].two_mounts_up = two_mounts_up;
+                    btry.groups[0].layout = GunLayoutType::Twin;
+
+                    // The weights are those of wgt_dist() split differently
+                    let total = ship.kg_dist().iter().fold(0.0, |total, (_, w, _)| total + w);
+                    assert_eq!(to_place(ship.hull.d(), 6), to_place(total, 6));
+                    assert_eq!(to_place(ship.hull.km() - ship.kg(), 6), to_place(ship.gm(), 6));
+
+                    // Raising the guns or adding weight up top raises the centre of gravity
+                    let mut lower = ship.clone();
+                    lower.wgts.above = 0;
+                    lower.batteries[0].groups[0].above = 0;
+                    lower.batteries[0].groups[0].on = above + on;
+                    if above > 0 || misc_above > 0 {
+                        assert!(ship.kg() > lower.kg());
+                    } else {
+                        assert_eq!(ship.kg(), lower.kg());
+                    }
+                }
+            )*
+        }
+    }
+
+    test_kg_dist! {
+        // name:             (above, on, two_mounts_up, misc above deck)
+        kg_dist_on_deck:     (0, 4, false, 0),
+        kg_dist_superfiring: (2, 2, false, 0),
+        kg_dist_two_up:      (2, 2, true, 0),
+        kg_dist_misc_above:  (0, 4, false, 200),
+    }
+
+    // Test trim {{{3
+    macro_rules! test_trim {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, distribution, fc) = $value;
+
+                    let mut ship = get_ship(1920, get_hull(), (12, 14.0, 45.0, 4));
+                    (ship.hull.fc_fwd, ship.hull.fc_aft) = (fc, fc);
+                    (ship.hull.fd_fwd, ship.hull.fd_aft) = (15.0, 15.0);
+                    (ship.hull.ad_fwd, ship.hull.ad_aft) = (15.0, 15.0);
+                    (ship.hull.qd_fwd, ship.hull.qd_aft) = (15.0, 15.0);
+
+                    let btry = &mut ship.batteries[0];
+                    btry.groups[0].layout = GunLayoutType::Triple;
+                    btry.groups[0].distribution = distribution;
+
+                    // The weights are those of kg_dist() placed along the length
+                    let total = ship.lcg_dist().iter().fold(0.0, |total, (_, w, _)| total + w);
+                    assert_eq!(to_place(ship.hull.d(), 6), to_place(total, 6));
+
+                    let caution = ship.report().lines()
+                        .find(|l| l.starts_with("Caution: Trimmed"))
+                        .unwrap_or("")
+                        .to_string();
+                    assert_eq!(expected, caution);
+                }
+            )*
+        }
+    }
+
+    test_trim! {
+        // name:          (caution, distribution, forecastle freeboard)
+        trim_even:        ("", GunDistributionType::CenterlineEven, 15.0),
+        trim_head:        ("Caution: Trimmed by the head - ballast needed", GunDistributionType::CenterlineFD, 30.0),
+        trim_head_wet:    ("Caution: Trimmed by the head with a low bow - very wet forward", GunDistributionType::CenterlineFD, 15.0),
+        trim_stern:       ("Caution: Trimmed by the stern - ballast needed", GunDistributionType::CenterlineAD, 15.0),
+    }
+
+    // Test trim_desc {{{3
+    macro_rules! test_trim_desc {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, trim, lcg) = $value;
+
+                    assert_eq!(expected, Ship::trim_desc(trim, lcg));
+                }
+            )*
+        }
+    }
+
+    test_trim_desc! {
+        // name:              (description, trim, lcg)
+        trim_desc_even:       ("on an even keel, centre of gravity at midships", 0.0, 0.0),
+        trim_desc_rounded:    ("on an even keel, centre of gravity at midships", -0.04, 0.049),
+        trim_desc_head:       ("1.2 ft / 0.4 m by the head, centre of gravity 3.0 ft / 0.9 m forward of midships", 1.2, 3.0),
+        trim_desc_stern:      ("0.5 ft / 0.2 m by the stern, centre of gravity 2.5 ft / 0.8 m abaft midships", -0.5, -2.5),
+    }
+
+    // Test girder {{{3
+    #[test]
+    fn girder() {
+        let mut ship = get_ship(1920, get_hull(), (8, 12.0, 45.0, 4));
+        (ship.hull.fd_fwd, ship.hull.fd_aft) = (15.0, 15.0);
+        (ship.hull.ad_fwd, ship.hull.ad_aft) = (15.0, 15.0);
+        ship.batteries[0].groups[0].layout = GunLayoutType::Twin;
+
+        // Every weight of kg_dist() is carried, the battery split by mount
+        let girder = ship.girder();
+        let total = girder.weights.iter().fold(0.0, |total, (w, _, _)| total + w);
+        assert_eq!(to_place(ship.hull.d(), 6), to_place(total, 6));
+        assert_eq!(4 + ship.kg_dist().len() - 1, girder.weights.len());
+
+        // A wave crest amidships adds to the hogging of the fine ends
+        assert!(ship.strength(Wave::Hogging).max_moment() > ship.strength(Wave::Still).max_moment());
+        assert!(ship.hull_stress(Wave::Hogging) > ship.hull_stress(Wave::Sagging));
+        assert!(ship.section_modulus() > 0.0);
+
+        // A deeper hull is a stiffer girder
+        let mut deep = ship.clone();
+        (deep.hull.fd_fwd, deep.hull.fd_aft) = (25.0, 25.0);
+        (deep.hull.ad_fwd, deep.hull.ad_aft) = (25.0, 25.0);
+        assert!(deep.section_modulus() > ship.section_modulus());
+
+        assert!(ship.report().contains("        - Hogging: "));
+    }
+
+    // Test girder_undistributed {{{3
+    #[test]
+    fn girder_undistributed() {
+        let mut ship = get_ship(1920, get_hull(), (8, 12.0, 45.0, 4));
+
+        // Mounts that are not placed in any group
+        ship.batteries[0].groups[0].on = 0;
+        ship.batteries[0].groups[0].layout = GunLayoutType::Twin;
+
+        let girder = ship.girder();
+        let total = girder.weights.iter().fold(0.0, |total, (w, _, _)| total + w);
+        assert_eq!(to_place(ship.hull.d(), 6), to_place(total, 6));
+        assert_eq!(ship.kg_dist().len(), girder.weights.len());
+    }
+
+    // Test plants {{{3
+    #[test]
+    fn plants() {
+        let mut ship = get_ship(1930, get_hull(), (0, 0.0, 0.0, 0));
+        ship.engine.fuel = FuelType::Oil;
+        ship.engine.boiler = BoilerType::Turbine;
+        ship.engine.drive = DriveType::Geared;
+        ship.engine.vmax = 30.0;
+        ship.engine.vcruise = 12.0;
+        ship.engine.range = 6000;
+        let turbines = ship.clone();
+
+        ship.engine.plants.push(Plant {
+            year: 1930,
+            fuel: FuelType::Diesel,
+            drive: DriveType::Direct,
+            hp: 8000.0,
+            role: PlantRole::Cruise,
+            ..Default::default()
+        });
+
+        // Cruising diesels burn less fuel but add weight
+        assert!(ship.wgt_bunker() < turbines.wgt_bunker());
+        assert!(ship.wgt_engine() > turbines.wgt_engine());
+
+        let report = ship.report();
+        assert!(report.contains("    Cruise plant: Diesel internal combustion motors, Direct drive, 8,000 bhp"), "{}", report);
+        assert!(report.contains("        - Cruise plant: 8,000 bhp = "), "{}", report);
+        assert!(report.contains(" tons oil, "), "{}", report);
+        assert!(!turbines.report().contains("        - Main plant: "));
+    }
+
+    // Test rig {{{3
+    macro_rules! test_rig {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, kind, vmax) = $value;
+
+                    let mut ship = get_ship(1865, get_hull(), (8, 9.0, 14.0, 8));
+                    ship.engine.vmax = vmax;
+                    ship.engine.vcruise = vmax / 2.0;
+                    ship.engine.range = 1500;
+                    ship.engine.boiler = BoilerType::Simple;
+                    ship.engine.fuel = FuelType::Coal;
+                    ship.engine.pct_coal = 1.0;
+
+                    let bare = ship.clone();
+
+                    ship.rig.kind = kind;
+
+                    // The rig comes out of the hull weight and sits high up
+                    let total = ship.wgt_dist().iter().fold(0.0, |total, (_, w)| total + w);
+                    assert_eq!(to_place(ship.hull.d(), 6), to_place(total, 6));
+                    if kind == RigType::None {
+                        assert_eq!(bare.stability_adj(), ship.stability_adj());
+                        assert_eq!(bare.kg(), ship.kg());
+                    } else {
+                        assert!(ship.stability_adj() < bare.stability_adj());
+                        assert!(ship.kg() > bare.kg());
+                        assert!(ship.sail_speed() > 0.0);
+                    }
+
+                    let report = ship.report();
+                    let line = report.lines()
+                        .find(|l| l.ends_with("kts under sail") || l.contains("Immobile"))
+                        .unwrap_or("");
+                    assert!(line.starts_with(expected), "{}", line);
+                }
+            )*
+        }
+    }
+
+    test_rig! {
+        // name:          (report, rig, vmax)
+        rig_none:         ("", RigType::None, 12.0),
+        rig_none_immobile: ("    Immobile floating battery", RigType::None, 0.0),
+        rig_full:         ("    Full rig, 55,000 sq ft / 5,110 sq m of sail, 275 tons", RigType::FullRigged, 12.0),
+        rig_sail_only:    ("    Barque rig, 40,000 sq ft", RigType::Barque, 0.0),
+    }
+
+    // Test submarine {{{3
+    macro_rules! test_submarine {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (speed, endurance, range, battery, motor_hp) = $value;
+
+                    // Type VIIC
+                    let mut hull = Hull::default();
+                    hull.set_lwl(210.0);
+                    hull.b = 20.3;
+                    hull.bb = 20.3;
+                    hull.t = 15.7;
+                    hull.set_d(770.0);
+                    hull.fd_fwd = 5.0;
+                    hull.fd_aft = 5.0;
+                    hull.ad_fwd = 5.0;
+                    hull.ad_aft = 5.0;
+
+                    let mut ship = get_ship(1940, hull, (1, 3.5, 45.0, 1));
+                    ship.mode = ShipKind::Submarine;
+                    ship.engine.fuel = FuelType::Diesel;
+                    ship.engine.vmax = 17.0;
+                    ship.engine.vcruise = 10.0;
+                    ship.engine.range = 8500;
+                    ship.pressure_hull.battery = battery;
+                    ship.pressure_hull.motor_hp = motor_hp;
+
+                    assert_eq!(speed, to_place(ship.sub_speed(), 1));
+                    assert_eq!(endurance, to_place(ship.sub_endurance(), 1));
+                    assert_eq!(range, to_place(ship.sub_range(), 0));
+
+                    // Batteries and motors come out of the hull weight
+                    let total = ship.wgt_dist().iter().fold(0.0, |total, (_, w)| total + w);
+                    assert_eq!(to_place(ship.hull.d(), 6), to_place(total, 6));
+                    assert!(ship.d_sub() > ship.hull.d());
+
+                    let report = ship.report();
+                    assert!(report.contains("t surfaced;"), "{}", report);
+                    assert!(report.contains("Test depth"), "{}", report);
+                    assert!(!report.contains("Armour:"), "{}", report);
+                    assert!(!report.contains("Overall survivability"), "{}", report);
+                }
+            )*
+        }
+    }
+
+    test_submarine! {
+        // name:          (speed, endurance, range, battery, motor hp)
+        sub_viic:         (8.4, 1.3, 103.0, 62.0, 750.0),
+        sub_small_bty:    (8.4, 0.6, 50.0, 30.0, 750.0),
+        sub_no_motors:    (0.0, 0.0, 0.0, 62.0, 0.0),
+    }
+
+    // Test aviation {{{3
+    macro_rules! test_aviation {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (failure, avn) = $value;
+
+                    let mut ship = get_ship(1935, get_hull(), (8, 5.0, 38.0, 4));
+                    ship.engine.vmax = 30.0;
+                    ship.engine.vcruise = 15.0;
+                    ship.engine.range = 8000;
+                    ship.hull.fd_fwd = 15.0;
+                    ship.hull.fd_aft = 15.0;
+
+                    let bare = ship.clone();
+
+                    ship.aviation = avn;
+
+                    // Aviation comes out of the hull weight, sits high up and
+                    // takes up deck space
+                    let total = ship.wgt_dist().iter().fold(0.0, |total, (_, w)| total + w);
+                    assert_eq!(to_place(ship.hull.d(), 6), to_place(total, 6));
+                    assert!(ship.stability_adj() < bare.stability_adj());
+                    assert!(ship.deck_room() <= bare.deck_room());
+                    assert!(ship.cost_dollar() > bare.cost_dollar());
+                    assert!(ship.d_lite() <= bare.d_lite());
+
+                    // Aviation gasoline is loaded with the bunker
+                    assert_eq!(to_place(ship.hull.d(), 6), to_place(ship.d_load(&LoadType::Normal), 6));
+                    assert_eq!(to_place(ship.d_std(), 6), to_place(ship.d_load(&LoadType::Standard), 6));
+                    assert_eq!(to_place(ship.d_max(), 6), to_place(ship.d_load(&LoadType::Full), 6));
+
+                    let report = ship.report();
+                    assert!(report.contains("Aviation:"), "{}", report);
+                    assert_eq!(failure, report.contains("DESIGN FAILURE: Flight deck"), "{}", report);
+                }
+            )*
+        }
+    }
+
+    test_aviation! {
+        // name:          (failure, aviation)
+        avn_cruiser:      (false, Aviation {
+            catapults: 2, cranes: 2, hangar: 20_000.0, aircraft: 4, aircraft_wgt: 6_000.0, avgas: 20.0,
+            ..Default::default()
+        }),
+        avn_deck:         (false, Aviation {
+            deck_len: 450.0, deck_wid: 55.0, aircraft: 20, aircraft_wgt: 6_000.0, avgas: 100.0,
+            ..Default::default()
+        }),
+        avn_deck_long:    (true, Aviation { deck_len: 600.0, deck_wid: 55.0, ..Default::default() }),
+    }
+
+    // Test surface_report {{{3
+    #[test]
+    fn surface_report() {
+        let mut ship = get_ship(1920, get_hull(), (0, 0.0, 0.0, 0));
+        ship.add_battery().num = 0;
+
+        let report = ship.report();
+        assert!(report.contains("t full load"), "{}", report);
+        assert!(report.contains("Armour:"), "{}", report);
+        assert!(!report.contains("Pressure hull:"), "{}", report);
+    }
+
+    // Test gz_curves {{{3
+    #[test]
+    fn gz_curves() {
+        let mut ship = get_ship(1920, get_hull(), (0, 0.0, 0.0, 0));
+        ship.engine.vmax = 25.0;
+        ship.engine.vcruise = 10.0;
+        ship.engine.range = 5000;
+        ship.loads.push(LoadType::Custom(50.0, 50.0));
+
+        // Give the deck amidships some height above the waterline
+        ship.hull.fd_fwd = 15.0;
+        ship.hull.fd_aft = 15.0;
+        ship.hull.ad_fwd = 15.0;
+        ship.hull.ad_aft = 15.0;
+
+        // Normal load is the centre of gravity of kg_dist()
+        assert_eq!(to_place(ship.kg(), 6), to_place(ship.kg_load(&LoadType::Normal), 6));
+
+        let curves = ship.gz_curves();
+        assert_eq!(ship.loads_all().len(), curves.len());
+        for (curve, load) in curves.iter().zip(ship.loads_all()) {
+            let (d, t) = (ship.d_load(&load), ship.t_load(&load));
+
+            assert_eq!(load.to_string(), curve.label);
+            assert_eq!(to_place(ship.hull.km_calc(d, t) - ship.kg_load(&load), 6), to_place(curve.gm, 6));
+            assert!(curve.range() > curve.max().heel && curve.max().gz > 0.0);
+        }
+
+        // Fuel and stores are low in the ship so a light ship is top heavy
+        assert!(curves[0].kg > curves[3].kg);
+
+        let csv = ship.gz_csv();
+        assert_eq!(Some("condition,heel_deg,gz_ft"), csv.lines().next());
+        assert!(csv.contains("\"50% bunker, 50% magazine\",1.0,"));
+        assert_eq!(curves.len(), ship.gz_svg().matches("<path").count());
+    }
+
+    // Test add_remove {{{3
+    #[test]
+    fn add_remove() {
+        let mut ship = Ship::default();
+        assert!(ship.batteries.is_empty() && ship.torps.is_empty() && ship.asw.is_empty());
+
+        for diam in [14.0, 6.0, 3.0] { ship.add_battery().diam = diam; }
+        ship.add_torps().num = 4;
+        ship.add_asw().num = 2;
+
+        assert_eq!(6.0, ship.remove_battery(1).unwrap().diam);
+        assert_eq!(vec![14.0, 3.0], ship.batteries.iter().map(|b| b.diam).collect::<Vec<_>>());
+        assert!(ship.remove_battery(2).is_none());
+        assert_eq!(4, ship.remove_torps(0).unwrap().num);
+        assert!(ship.remove_torps(0).is_none());
+        assert_eq!(2, ship.remove_asw(0).unwrap().num);
+    }
+
+    // Test sship_layout {{{3
+    macro_rules! test_sship_layout {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (batteries, empty, ok) = $value;
+
+                    let mut ship = Ship::default();
+                    for _ in 0..batteries { ship.add_battery().num = 2; }
+                    for _ in 0..empty { ship.add_battery(); }
+                    ship.add_torps().num = 4;
+
+                    match ship.sship_layout() {
+                        Ok(s) => {
+                            assert!(ok);
+                            assert_eq!(Ship::SS_BATTERIES, s.batteries.len());
+                            assert_eq!(Ship::SS_TORPS, s.torps.len());
+                            assert_eq!(Ship::SS_ASW, s.asw.len());
+                            assert_eq!(batteries, s.batteries.iter().filter(|b| b.num > 0).count());
+                            assert_eq!(4, s.torps[0].num);
+                        },
+                        Err(_) => assert!(!ok),
+                    }
+                }
+            )*
+        }
+    }
+
+    test_sship_layout! {
+        // name:                    (batteries, empty, ok)
+        sship_layout_none:          (0, 0, true),
+        sship_layout_padded:        (2, 0, true),
+        sship_layout_full:          (5, 0, true),
+        sship_layout_empty_extra:   (5, 2, true),
+        sship_layout_too_many:      (6, 0, false),
+    }
+
+    // Test sship_layout_groups {{{3
+    macro_rules! test_sship_layout_groups {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (groups, ok) = $value;
+
+                    let mut ship = Ship::default();
+                    let b = ship.add_battery();
+                    b.num = 2 * groups as u32;
+                    b.groups.clear();
+                    for _ in 0..groups { b.add_group().on = 1; }
+
+                    match ship.sship_layout() {
+                        Ok(s) => {
+                            assert!(ok);
+                            assert!(s.batteries.iter().all(|b| b.groups.len() == Battery::SS_GROUPS));
+                        },
+                        Err(_) => assert!(!ok),
+                    }
+                }
+            )*
+        }
+    }
+
+    test_sship_layout_groups! {
+        // name:                        (groups, ok)
+        sship_layout_groups_one:        (1, true),
+        sship_layout_groups_two:        (2, true),
+        sship_layout_groups_three:      (3, false),
+    }
+
+    // Test report_groups {{{3
+    #[test]
+    fn report_groups() {
+        let mut ship = get_ship(1920, get_hull(), (9, 12.0, 45.0, 4));
+
+        // Twin turrets fore, a triple aft and single wing mounts
+        let b = &mut ship.batteries[0];
+        b.groups.clear();
+        for (layout, distribution, on) in [
+            (GunLayoutType::Twin, GunDistributionType::CenterlineFD, 1),
+            (GunLayoutType::Triple, GunDistributionType::CenterlineAD, 1),
+            (GunLayoutType::Single, GunDistributionType::SidesEven, 2),
+        ] {
+            let g = b.add_group();
+            g.layout = layout;
+            g.distribution = distribution;
+            g.on = on;
+        }
+
+        let report = ship.report();
+        assert!(report.contains("1 x Twin mount on centreline, forward"));
+        assert!(report.contains("1 x Triple mount on centreline, aft"));
+        assert!(report.contains("2 x Single mounts on sides"));
+        assert!(!report.contains("WARNING"));
+
+        ship.batteries[0].mount_num = 5;
+        assert!(ship.report().contains("WARNING: 4 of 5 mounts placed in groups"));
+    }
+
+    // Test ordinal {{{3
+    macro_rules! test_ordinal {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (i, expected) = $value;
+
+                    assert_eq!(expected, ordinal(i));
+                }
+            )*
+        }
+    }
+
+    test_ordinal! {
+        // name:        (index, label)
+        ordinal_main:   (0, "Main"),
+        ordinal_2nd:    (1, "2nd"),
+        ordinal_3rd:    (2, "3rd"),
+        ordinal_4th:    (3, "4th"),
+        ordinal_11th:   (10, "11th"),
+        ordinal_12th:   (11, "12th"),
+        ordinal_21st:   (20, "21st"),
+        ordinal_22nd:   (21, "22nd"),
+    }
+
+    // Test report_many_weapons {{{3
+    #[test]
+    fn report_many_weapons() {
+        let mut ship = get_ship(1920, get_hull(), (0, 0.0, 0.0, 0));
+
+        for diam in [12.0, 6.0, 4.0, 3.0, 2.0, 1.0] {
+            let b = ship.add_battery();
+            b.num = 2;
+            b.diam = diam;
+            b.len = 45.0;
+            b.mount_num = 2;
+            b.groups[0].on = 2;
+        }
+        for _ in 0..3 {
+            let t = ship.add_torps();
+            t.year = 1920;
+            t.num = 2;
+            t.mounts = 1;
+            t.diam = 21.0;
+            t.len = 20.0;
+        }
+
+        let report = ship.report();
+        assert!(report.contains("3rd Torpedoes"));
+        assert_eq!(6, ship.ship_report().batteries.len());
+    }
+
+    // Test d_load {{{3
+    macro_rules! test_d_load {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (load, expected): (LoadType, fn(&Ship) -> f64) = $value;
+
+                    let mut ship = get_ship(1920, get_hull(), (4, 8.0, 45.0, 4));
+                    ship.engine.vmax = 20.0;
+                    ship.engine.vcruise = 10.0;
+                    ship.engine.range = 5000;
+
+                    assert_eq!(to_place(expected(&ship), 3), to_place(ship.d_load(&load), 3));
+                }
+            )*
+        }
+    }
+
+    test_d_load! {
+        // name:            (load, expected displacement)
+        d_load_light:       (LoadType::Light, |s| s.d_lite()),
+        d_load_standard:    (LoadType::Standard, |s| s.d_std()),
+        d_load_normal:      (LoadType::Normal, |s| s.hull.d()),
+        d_load_full:        (LoadType::Full, |s| s.d_max()),
+        d_load_custom_full: (LoadType::Custom(100.0, 100.0), |s| s.d_max()),
+        d_load_custom_std:  (LoadType::Custom(0.0, 100.0), |s| s.d_std()),
+        d_load_custom_none: (LoadType::Custom(0.0, 0.0), |s| s.d_lite() + s.hull.d() * 0.02),
+    }
+
+    // Test load_calc {{{3
+    macro_rules! test_load_calc {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (normal, calc): (fn(&Ship) -> f64, fn(&Ship, f64, f64) -> f64) = $value;
+
+                    let mut ship = get_ship(1920, get_hull(), (4, 8.0, 45.0, 4));
+                    ship.engine.vmax = 20.0;
+                    ship.engine.vcruise = 10.0;
+                    ship.engine.range = 5000;
+
+                    let load = LoadType::Normal;
+                    let (d, t) = (ship.d_load(&load), ship.t_load(&load));
+
+                    assert_eq!(to_place(normal(&ship), 3), to_place(calc(&ship, d, t), 3));
+                }
+            )*
+        }
+    }
+
+    test_load_calc! {
+        // name:                    (value at normal, value for d and t)
+        load_calc_stability:        (|s| s.stability_adj(), |s, d, t| s.stability_adj_calc(d, t)),
+        load_calc_metacenter:       (|s| s.metacenter(), |s, d, t| s.metacenter_calc(d, t)),
+        load_calc_steadiness:       (|s| s.steadiness(), |s, d, t| s.steadiness_calc(d, t)),
+        load_calc_seakeeping:       (|s| s.seakeeping(), |s, d, t| s.seakeeping_calc(d, t)),
+        load_calc_roll_period:      (|s| s.roll_period(), |s, d, t| s.roll_period_calc(d, t)),
+        load_calc_damage_shell_num: (|s| s.damage_shell_num(), |s, d, t| s.damage_shell_num_calc(d, t)),
+        load_calc_damage_torp_num:  (|s| s.damage_torp_num(), |s, d, t| s.damage_torp_num_calc(d, t)),
+    }
+}
+
+// ShipReport {{{1
+/// Key figures of a ship's report.
+///
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct ShipReport {
+    /// Name of ship.
+    pub name: String,
+    /// Country of ship.
+    pub country: String,
+    /// Type of ship.
+    pub kind: String,
+    /// Year ship laid down.
+    pub year: u32,
+
+    /// Standard displacement (t).
+    pub d_std: f64,
+    /// Normal displacement (t).
+    pub d: f64,
+    /// Full load displacement (t).
+    pub d_max: f64,
+    /// Maximum speed (kts).
+    pub vmax: f64,
+
+    /// Batteries with guns.
+    pub batteries: Vec<BatteryReport>,
+    /// Sets of torpedoes.
+    pub torps: Vec<TorpedoReport>,
+    /// Charge the torpedo protection on each side withstands (lbs).
+    pub tds_charge: f64,
+    /// Weight of broadside (lbs).
+    pub wgt_broad: f64,
+    /// Shells fired per minute by all batteries.
+    pub shells_min: f64,
+    /// Weight of fire per minute (lbs).
+    pub wgt_min: f64,
+
+    /// Long range anti-aircraft score.
+    pub aa_long: f64,
+    /// Medium range anti-aircraft score.
+    pub aa_medium: f64,
+    /// Short range anti-aircraft score.
+    pub aa_short: f64,
+}
+
+// BatteryReport {{{1
+/// Key figures of one battery in a ShipReport.
+///
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct BatteryReport {
+    /// Number of guns.
+    pub num: u32,
+    /// Gun diameter (in).
+    pub diam: f64,
+    /// Type of gun.
+    pub kind: String,
+    /// Weight of each shell (lbs).
+    pub shell_wgt: f64,
+    /// Rounds per minute per gun.
+    pub rof: f64,
+    /// Shells fired per minute by the battery.
+    pub shells_min: f64,
+    /// Weight of fire per minute (lbs).
+    pub wgt_min: f64,
+    /// Maximum elevation (degrees).
+    pub max_elevation: f64,
+    /// Maximum range (yds).
+    pub max_range: f64,
+    /// Mix of shells in the magazine.
+    pub magazine: String,
+    /// Guns bearing ahead, astern and on each broadside.
+    pub bearing: Bearing,
+}
+
+// TorpedoReport {{{1
+/// Key figures of one set of torpedoes in a ShipReport.
+///
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct TorpedoReport {
+    /// Number of torpedoes.
+    pub num: u32,
+    /// Torpedo diameter (in).
+    pub diam: f64,
+    /// Torpedo length (ft).
+    pub len: f64,
+    /// Year torpedo was designed.
+    pub year: u32,
+    /// Warhead weight (lbs).
+    pub warhead: f64,
+    /// Speed (kts) and range (yds) settings.
+    pub settings: Vec<(f64, f64)>,
+    /// Hits by this torpedo needed to sink the ship itself, if it has a
+    /// warhead.
+    pub hits: Option<f64>,
+}
+
+// Hydrostatics {{{1
+/// Hydrostatic properties of a ship at one displacement.
+///
+#[derive(Clone, Debug, Default)]
+pub struct Hydrostatics {
+    /// Name of the loading condition, if any.
+    pub label: String,
+    /// Displacement (t).
+    pub d: f64,
+    /// Draft (ft).
+    pub t: f64,
+    /// Block Coefficient.
+    pub cb: f64,
+    /// Waterplane Area (ft²).
+    pub wp: f64,
+    /// Metacentric height (ft).
+    pub metacenter: f64,
+    /// Stability (unstable if below 1.0).
+    pub stability: f64,
+}
+
+// LoadType {{{1
+/// Loading conditions of bunkerage, magazines and stores.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub enum LoadType {
+    /// No bunkerage, magazines or stores.
+    Light,
+    /// Full magazines and stores but no bunkerage.
+    Standard,
+    /// Normal bunkerage, full magazines and stores.
+    #[default]
+    Normal,
+    /// Full bunkerage, magazines and stores.
+    Full,
+    /// Percentages of full bunkerage and magazines, with full stores.
+    /// Percentages outside 0 to 100 are clamped to that range.
+    Custom(f64, f64),
+}
+
+impl fmt::Display for LoadType { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Light    => write!(f, "Light"),
+            Self::Standard => write!(f, "Standard"),
+            Self::Normal   => write!(f, "Normal"),
+            Self::Full     => write!(f, "Full load"),
+            Self::Custom(bunker, mag) =>
+                write!(f, "{:.0}% bunker, {:.0}% magazine", Self::pct(*bunker), Self::pct(*mag)),
+        }
+    }
+}
+
+impl LoadType { // {{{2
+    /// Bunkerage at maximum displacement as a multiple of normal bunkerage.
+    const BUNKER_FULL: f64 = 1.8;
+
+    // pct {{{3
+    /// Clamp a user supplied percentage to 0-100.
+    ///
+    fn pct(n: f64) -> f64 {
+        n.clamp(0.0, 100.0)
+    }
+
+    // bunker {{{3
+    /// Bunkerage carried as a fraction of normal bunkerage.
+    ///
+    pub fn bunker(&self) -> f64 {
+        match self {
+            Self::Light | Self::Standard => 0.0,
+            Self::Normal                 => 1.0,
+            Self::Full                   => Self::BUNKER_FULL,
+            Self::Custom(bunker, _)      => Self::pct(*bunker) / 100.0 * Self::BUNKER_FULL,
+        }
+    }
+
+    // mag {{{3
+    /// Magazine carried as a fraction of full magazines.
+    ///
+    pub fn mag(&self) -> f64 {
+        match self {
+            Self::Light          => 0.0,
+            Self::Custom(_, mag) => Self::pct(*mag) / 100.0,
+            _                    => 1.0,
+        }
+    }
+
+    // stores {{{3
+    /// Stores carried as a fraction of full stores.
+    ///
+    pub fn stores(&self) -> f64 {
+        match self {
+            Self::Light => 0.0,
+            _           => 1.0,
+        }
+    }
+}
+
+// Testing LoadType {{{2
+#[cfg(test)]
+mod load_type {
+    use super::*;
+
+    // Test fractions {{{3
+    macro_rules! test_load_fractions {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (load, bunker, mag, stores) = $value;
+
+                    assert_eq!((bunker, mag, stores), (load.bunker(), load.mag(), load.stores()));
+                }
+            )*
+        }
+    }
+
+    test_load_fractions! {
+        // name:              (load, bunker, mag, stores)
+        load_light:           (LoadType::Light, 0.0, 0.0, 0.0),
+        load_standard:        (LoadType::Standard, 0.0, 1.0, 1.0),
+        load_normal:          (LoadType::Normal, 1.0, 1.0, 1.0),
+        load_full:            (LoadType::Full, 1.8, 1.0, 1.0),
+        load_custom:          (LoadType::Custom(50.0, 25.0), 0.9, 0.25, 1.0),
+        load_custom_negative: (LoadType::Custom(-50.0, -25.0), 0.0, 0.0, 1.0),
+        load_custom_over:     (LoadType::Custom(150.0, 125.0), 1.8, 1.0, 1.0),
+    }
+
+    // Test display {{{3
+    macro_rules! test_load_display {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (load, expected) = $value;
+
+                    assert_eq!(expected, load.to_string());
+                }
+            )*
+        }
+    }
+
+    test_load_display! {
+        // name:              (load, string)
+        load_display_full:    (LoadType::Full, "Full load"),
+        load_display_custom:  (LoadType::Custom(50.0, 25.0), "50% bunker, 25% magazine"),
+        load_display_clamped: (LoadType::Custom(150.0, -25.0), "100% bunker, 0% magazine"),
+    }
 }
 
 // SeaType {{{1
@@ -1,8 +1,12 @@
 use clap::{Parser, Subcommand};
 use rfd::FileDialog;
-use sharpie::{Ship, SHIP_FILE_EXT, SS_SHIP_FILE_EXT};
+use sharpie::{Ship, Gun, Duel, Flooding, Strike, ShellType, Treaty, SHIP_FILE_EXT, SS_SHIP_FILE_EXT};
+use slint::{Color, ModelRc, VecModel};
 
+use std::cell::RefCell;
 use std::error::Error;
+use std::fs;
+use std::rc::Rc;
 
 slint::include_modules!();
 
@@ -25,7 +29,11 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
     Load {
-        file: String
+        file: String,
+
+        #[arg(short, long)]
+        #[arg(help = "Print the key figures of the report as JSON")]
+        json: bool,
     },
 
     Convert {
@@ -40,13 +48,170 @@ enum Commands {
         #[arg(help = "Show ship report after conversion")]
         report: bool,
     },
+
+    Curves {
+        file: String,
+
+        #[arg(short, long)]
+        #[arg(help = "Also plot the curves to an SVG file")]
+        svg: Option<String>,
+    },
+
+    Hydrostatics {
+        file: String
+    },
+
+    Gz {
+        file: String,
+
+        #[arg(short, long)]
+        #[arg(help = "Also plot the curves to an SVG file")]
+        svg: Option<String>,
+    },
+
+    Check {
+        file: String,
+
+        #[arg(short, long)]
+        #[arg(help = "washington1922, london1930, anglogerman1935 or london1936")]
+        treaty: String,
+    },
+
+    Range {
+        file: String,
+
+        #[arg(short, long, default_value_t = 0)]
+        #[arg(help = "Battery to print the range table of")]
+        battery: usize,
+
+        #[arg(short, long, default_value = "ap")]
+        #[arg(help = "Shell type: ap, common, he or shrapnel")]
+        shell: ShellType,
+    },
+
+    Iz {
+        file: String,
+
+        #[arg(short, long)]
+        #[arg(help = "Attacking gun as FILE[:BATTERY], battery 0 if omitted")]
+        gun: Option<String>,
+
+        #[arg(short, long)]
+        #[arg(help = "Attacking gun diameter (in), if --gun is not given")]
+        diam: Option<f64>,
+
+        #[arg(short, long, default_value_t = 45.0)]
+        #[arg(help = "Attacking gun length (calibers)")]
+        len: f64,
+
+        #[arg(short, long, default_value_t = 1920)]
+        #[arg(help = "Year attacking gun was designed")]
+        year: u32,
+
+        #[arg(short, long, default_value = "ap")]
+        #[arg(help = "Shell type: ap, common, he or shrapnel")]
+        shell: ShellType,
+    },
+
+    Tds {
+        file: String,
+
+        #[arg(help = "Reference design whose torpedoes are compared")]
+        reference: String,
+    },
+
+    Flood {
+        file: String,
+
+        #[arg(long = "hit", required = true)]
+        #[arg(help = "Hit as LOCATION:WEAPON[:SIDE], e.g. belt:14 or underwater:500lb:port")]
+        hits: Vec<Strike>,
+    },
+
+    Duel {
+        a: String,
+        b: String,
+
+        #[arg(short, long, default_value_t = 15_000.0)]
+        #[arg(help = "Range (yds)")]
+        range: f64,
+
+        #[arg(short, long, default_value_t = 1)]
+        #[arg(help = "Seed for the random number generator")]
+        seed: u64,
+
+        #[arg(short = 'n', long, default_value_t = 1_000)]
+        #[arg(help = "Number of duels to fight")]
+        runs: u32,
+    },
+}
+
+// Ship display {{{1
+//
+/// Colors used for the segments of the weight chart.
+///
+const WEIGHT_COLORS: [(u8, u8, u8); 15] = [
+    (0xc0, 0x39, 0x2b), (0xe6, 0x7e, 0x22), (0x2c, 0x3e, 0x50), (0x34, 0x49, 0x5e),
+    (0x5d, 0x6d, 0x7e), (0x7f, 0x8c, 0x8d), (0x95, 0xa5, 0xa6), (0x4a, 0x23, 0x5a),
+    (0x27, 0xae, 0x60), (0x29, 0x80, 0xb9), (0xf1, 0xc4, 0x0f), (0x8e, 0x44, 0xad),
+    (0x8b, 0x5a, 0x2b), (0x16, 0xa0, 0x85), (0x5d, 0xad, 0xe2),
+];
+
+/// Segments of the weight chart. Groups without any weight are dropped,
+/// including a negative hull weight on an overweight design.
+///
+fn weight_portions(ship: &Ship) -> Vec<WeightPortion> {
+    let d = ship.hull.d();
+
+    // Colors are assigned before empty groups are dropped so that each
+    // group keeps the same color from ship to ship
+    ship.wgt_dist().into_iter().enumerate()
+        .filter(|(_, (_, tons))| *tons > 0.0)
+        .map(|(i, (label, tons))| {
+            let (r, g, b) = WEIGHT_COLORS[i % WEIGHT_COLORS.len()];
+
+            WeightPortion {
+                label: label.into(),
+                tons: tons as f32,
+                percent: if d > 0.0 { (tons / d * 100.0) as f32 } else { 0.0 },
+                color: Color::from_rgb_u8(r, g, b),
+            }
+        })
+        .collect()
+}
+
+/// Ship shown in the GUI, if any.
+///
+type Current = Rc<RefCell<Option<Ship>>>;
+
+/// Show the ship report, weight distribution chart, speed curves and
+/// weapon counts.
+///
+fn show_ship(ui: &MainWindow, ship: &Ship) {
+    ui.set_report_str(ship.report().into());
+
+    ui.set_loaded(true);
+    ui.set_batteries(ship.batteries.len() as i32);
+    ui.set_torps(ship.torps.len() as i32);
+    ui.set_asw(ship.asw.len() as i32);
+
+    ui.set_weights(ModelRc::new(VecModel::from(weight_portions(ship))));
+
+    let (vmax, hp_max, range_max) = ship.curves_scale();
+    ui.set_curves(SpeedCurves {
+        hp_path: ship.curves_path(|p| p.hp, hp_max, 0.0, 0.0, 100.0, 100.0).into(),
+        range_path: ship.curves_path(|p| p.range, range_max, 0.0, 0.0, 100.0, 100.0).into(),
+        vmax: format!("{}", vmax).into(),
+        hp_max: format!("{}", hp_max).into(),
+        range_max: format!("{}", range_max).into(),
+    });
 }
 
 // Load and Convert {{{1
 //
 /// Convert a Springsharp 3b3 file to sharpie format and show the ship report.
 ///
-fn convert_ship(ui: MainWindow) {
+fn convert_ship(ui: MainWindow, current: Current) {
     let file = FileDialog::new()
         .set_title("Springsharp file to convert")
         .add_filter(SS_SHIP_FILE_EXT, &[SS_SHIP_FILE_EXT,])
@@ -59,8 +224,9 @@ fn convert_ship(ui: MainWindow) {
 
     match Ship::convert(file) {
         Ok(ship) => {
-            ui.set_report_str(ship.report().into());
-            save_ship(ship);
+            show_ship(&ui, &ship);
+            save_ship(&ship);
+            current.replace(Some(ship));
         },
 
         // TODO: Show errors in the GUI
@@ -70,7 +236,7 @@ fn convert_ship(ui: MainWindow) {
 
 /// Load a sharpie ship file and show the ship report.
 ///
-fn load_ship(ui: MainWindow) {
+fn load_ship(ui: MainWindow, current: Current) {
     let file = FileDialog::new()
         .set_title("Sharpie file to load")
         .add_filter(SHIP_FILE_EXT, &[SHIP_FILE_EXT,])
@@ -82,8 +248,10 @@ fn load_ship(ui: MainWindow) {
         .unwrap();
 
     match Ship::load(file) {
-        Ok(ship) =>
-            ui.set_report_str(ship.report().into()),
+        Ok(ship) => {
+            show_ship(&ui, &ship);
+            current.replace(Some(ship));
+        },
 
         // TODO: Show errors in the GUI
         Err(error) => eprintln!("{}", error),
@@ -92,7 +260,7 @@ fn load_ship(ui: MainWindow) {
 
 /// Save a ship to a file.
 ///
-fn save_ship(ship: Ship) {
+fn save_ship(ship: &Ship) {
     let file = FileDialog::new()
         .set_title("Sharpie file to save")
         .set_file_name("SHIP.".to_owned() + SHIP_FILE_EXT)
@@ -111,13 +279,34 @@ fn save_ship(ship: Ship) {
     };
 }
 
+// Edit {{{1
+//
+/// Change the current ship and show the result.
+///
+fn edit_ship(ui: MainWindow, current: Current, f: impl FnOnce(&mut Ship)) {
+    if let Some(ship) = current.borrow_mut().as_mut() {
+        f(ship);
+        show_ship(&ui, ship);
+    }
+}
+
 // Run the GUI {{{1
 //
 fn run_gui() -> Result<(), Box<dyn Error>> {
     let ui = MainWindow::new().unwrap();
+    let current: Current = Rc::new(RefCell::new(None));
+
+    ui.on_load_ship   ({ let h = ui.as_weak(); let c = current.clone(); move || { load_ship(h.unwrap(), c.clone()); }});
+    ui.on_convert_ship({ let h = ui.as_weak(); let c = current.clone(); move || { convert_ship(h.unwrap(), c.clone()); }});
+    ui.on_save_ship   ({ let c = current.clone(); move || { if let Some(ship) = c.borrow().as_ref() { save_ship(ship); } }});
 
-    ui.on_load_ship   ({ let h = ui.as_weak(); move || { load_ship(h.unwrap()); }});
-    ui.on_convert_ship({ let h = ui.as_weak(); move || { convert_ship(h.unwrap()); }});
+    // Weapons are added after and removed from the end of each list
+    ui.on_add_battery   ({ let h = ui.as_weak(); let c = current.clone(); move || { edit_ship(h.unwrap(), c.clone(), |s| { s.add_battery(); }); }});
+    ui.on_remove_battery({ let h = ui.as_weak(); let c = current.clone(); move || { edit_ship(h.unwrap(), c.clone(), |s| { s.batteries.pop(); }); }});
+    ui.on_add_torps     ({ let h = ui.as_weak(); let c = current.clone(); move || { edit_ship(h.unwrap(), c.clone(), |s| { s.add_torps(); }); }});
+    ui.on_remove_torps  ({ let h = ui.as_weak(); let c = current.clone(); move || { edit_ship(h.unwrap(), c.clone(), |s| { s.torps.pop(); }); }});
+    ui.on_add_asw       ({ let h = ui.as_weak(); let c = current.clone(); move || { edit_ship(h.unwrap(), c.clone(), |s| { s.add_asw(); }); }});
+    ui.on_remove_asw    ({ let h = ui.as_weak(); let c = current.clone(); move || { edit_ship(h.unwrap(), c.clone(), |s| { s.asw.pop(); }); }});
 
     match ui.run() {
         Ok(_) => Ok(()),
@@ -131,10 +320,14 @@ fn main() -> Result<(), Box<dyn Error>> {
     let cli = Cli::parse();
 
      match cli.command {
-        Some(Commands::Load { file }) => {
+        Some(Commands::Load { file, json }) => {
             match Ship::load(file) {
                 Ok(ship) => {
-                    println!("{}", ship.report());
+                    if json {
+                        println!("{}", serde_json::to_string_pretty(&ship.ship_report())?);
+                    } else {
+                        println!("{}", ship.report());
+                    }
                     #[cfg(debug_assertions)]
                     if cli.debug { eprintln!("{}", ship.internals()); }
 
@@ -166,6 +359,125 @@ fn main() -> Result<(), Box<dyn Error>> {
             }
         },
 
+        Some(Commands::Curves { file, svg }) => {
+            match Ship::load(file) {
+                Ok(ship) => {
+                    println!("{}", ship.curves_csv());
+
+                    match svg {
+                        Some(svg) => match fs::write(svg, ship.curves_svg()) {
+                            Ok(_) => Ok(()),
+                            Err(error) => Err(error.into()),
+                        },
+
+                        None => Ok(()),
+                    }
+                },
+
+                Err(error) => Err(error),
+            }
+        },
+
+        Some(Commands::Hydrostatics { file }) => {
+            match Ship::load(file) {
+                Ok(ship) => {
+                    println!("{}", ship.hydrostatics_table());
+
+                    Ok(())
+                },
+
+                Err(error) => Err(error),
+            }
+        },
+
+        Some(Commands::Gz { file, svg }) => {
+            let ship = Ship::load(file)?;
+
+            println!("{}", ship.gz_csv());
+
+            match svg {
+                Some(svg) => match fs::write(svg, ship.gz_svg()) {
+                    Ok(_) => Ok(()),
+                    Err(error) => Err(error.into()),
+                },
+
+                None => Ok(()),
+            }
+        },
+
+        Some(Commands::Check { file, treaty }) => {
+            let treaty: Treaty = treaty.parse()?;
+
+            match Ship::load(file) {
+                Ok(ship) => {
+                    println!("{}", treaty.report(&ship));
+
+                    Ok(())
+                },
+
+                Err(error) => Err(error),
+            }
+        },
+
+        Some(Commands::Range { file, battery, shell }) => {
+            let ship = Ship::load(file)?;
+
+            println!("{}", Gun::from_ship(&ship, battery)?.with_shell(shell).range_report());
+
+            Ok(())
+        },
+
+        Some(Commands::Iz { file, gun, diam, len, year, shell }) => {
+            let target = Ship::load(file)?;
+
+            let gun = match (gun, diam) {
+                (Some(gun), _) => {
+                    // Only a numeric suffix is a battery, so paths such as
+                    // C:\ships\a.ship are left whole
+                    let (file, battery) = match gun.rsplit_once(':') {
+                        Some((file, n)) => match n.parse::<usize>() {
+                            Ok(n)  => (file.to_string(), n),
+                            Err(_) => (gun, 0),
+                        },
+                        None => (gun, 0),
+                    };
+                    Gun::from_ship(&Ship::load(file)?, battery)?
+                },
+                (None, Some(diam)) => Gun::new(diam, len, year),
+                (None, None) => return Err("Either --gun or --diam is required".into()),
+            };
+
+            println!("{}", gun.with_shell(shell).iz_report(&target));
+
+            Ok(())
+        },
+
+        Some(Commands::Tds { file, reference }) => {
+            let ship = Ship::load(file)?;
+            let reference = Ship::load(reference)?;
+
+            println!("{}", ship.tds_report(&reference));
+
+            Ok(())
+        },
+
+        Some(Commands::Flood { file, hits }) => {
+            let ship = Ship::load(file)?;
+
+            println!("{}", Flooding::new(&ship, hits).report());
+
+            Ok(())
+        },
+
+        Some(Commands::Duel { a, b, range, seed, runs }) => {
+            let a = Ship::load(a)?;
+            let b = Ship::load(b)?;
+
+            println!("{}", Duel::new(&a, &b, range).report(seed, runs));
+
+            Ok(())
+        },
+
         // No subcommand means launch the GUI
         None => run_gui(),
     }
@@ -180,3 +492,39 @@ fn verify_cli() {
     Cli::command().debug_assert();
 }
 
+#[test]
+fn weight_portions_overweight() {
+    let mut ship = Ship::default();
+    ship.hull.set_d(1_000.0);
+    ship.hull.set_lwl(250.0);
+    ship.hull.b = 25.0;
+    ship.hull.bb = 25.0;
+    ship.hull.t = 10.0;
+
+    ship.engine.year = 1920;
+    ship.engine.vmax = 30.0;
+    ship.engine.vcruise = 10.0;
+    ship.engine.range = 5000;
+
+    ship.add_battery();
+    ship.batteries[0].num = 8;
+    ship.batteries[0].diam = 16.0;
+    ship.batteries[0].len = 45.0;
+    ship.batteries[0].mount_num = 4;
+    ship.batteries[0].groups[0].on = 4;
+
+    let dist = ship.wgt_dist();
+    let hull = dist.iter().position(|(label, _)| label == "Hull, fittings & equipment").unwrap();
+    assert!(dist[hull].1 < 0.0);
+
+    let portions = weight_portions(&ship);
+    assert!(portions.iter().all(|p| p.tons > 0.0));
+    assert!(portions.iter().all(|p| p.label != dist[hull].0.as_str()));
+
+    // Each group keeps the color of its place in wgt_dist()
+    for p in portions.iter() {
+        let i = dist.iter().position(|(label, _)| label == p.label.as_str()).unwrap();
+        let (r, g, b) = WEIGHT_COLORS[i];
+        assert_eq!(Color::from_rgb_u8(r, g, b), p.color);
+    }
+}
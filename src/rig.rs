@@ -0,0 +1,154 @@
+use serde::{Serialize, Deserialize};
+
+use std::fmt;
+
+// RigType {{{1
+/// Sail plan carried by the ship.
+///
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum RigType {
+    /// No sails.
+    #[default]
+    None,
+    /// Light fore and aft rig to steady the ship and eke out the coal.
+    Auxiliary,
+    /// Square rig on the fore and main masts, fore and aft on the mizzen.
+    Barque,
+    /// Square rig on every mast.
+    FullRigged,
+}
+
+impl RigType { // {{{2
+    // area_factor {{{3
+    /// Usual sail area as a multiple of waterline length times beam.
+    ///
+    fn area_factor(&self) -> f64 {
+        match self {
+            Self::None       => 0.0,
+            Self::Auxiliary  => 0.8,
+            Self::Barque     => 1.6,
+            Self::FullRigged => 2.2,
+        }
+    }
+
+    // efficiency {{{3
+    /// Drive of the sails relative to a full square rig.
+    ///
+    fn efficiency(&self) -> f64 {
+        match self {
+            Self::None       => 0.0,
+            Self::Auxiliary  => 0.8,
+            Self::Barque     => 0.9,
+            Self::FullRigged => 1.0,
+        }
+    }
+}
+
+impl fmt::Display for RigType { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::None       => "No",
+                Self::Auxiliary  => "Auxiliary",
+                Self::Barque     => "Barque",
+                Self::FullRigged => "Full",
+            }
+        )
+    }
+}
+
+// Rig {{{1
+/// Masts, yards and sails.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct Rig {
+    /// Type of rig.
+    pub kind: RigType,
+    /// Sail area (sq ft), or 0 for the usual area of the type of rig.
+    pub area: f64,
+}
+
+impl Rig { // {{{2
+    /// Weight of masts, yards and rigging per square foot of sail (t).
+    ///
+    const WGT_PER_AREA: f64 = 0.005;
+
+    /// Height of the weight of the rig above the deck per foot of the
+    /// square root of the sail area.
+    ///
+    const HGT_PER_AREA: f64 = 0.2;
+
+    /// Horsepower per square foot of sail in a fresh breeze.
+    ///
+    const HP_PER_AREA: f64 = 0.1;
+
+    // sail_area {{{3
+    /// Sail area (sq ft) of a ship of a given waterline length and beam.
+    ///
+    pub fn sail_area(&self, lwl: f64, bb: f64) -> f64 {
+        if self.kind == RigType::None { return 0.0; }
+
+        if self.area > 0.0 {
+            self.area
+        } else {
+            self.kind.area_factor() * lwl * bb
+        }
+    }
+
+    // wgt {{{3
+    /// Weight of masts, yards and rigging (t).
+    ///
+    pub fn wgt(&self, lwl: f64, bb: f64) -> f64 {
+        self.sail_area(lwl, bb) * Self::WGT_PER_AREA
+    }
+
+    // hgt {{{3
+    /// Height of the weight of the rig above the deck (ft).
+    ///
+    pub fn hgt(&self, lwl: f64, bb: f64) -> f64 {
+        self.sail_area(lwl, bb).sqrt() * Self::HGT_PER_AREA
+    }
+
+    // hp {{{3
+    /// Horsepower the sails give in a fresh breeze.
+    ///
+    pub fn hp(&self, lwl: f64, bb: f64) -> f64 {
+        self.sail_area(lwl, bb) * self.kind.efficiency() * Self::HP_PER_AREA
+    }
+}
+
+// Testing Rig {{{2
+#[cfg(test)]
+mod rig_type {
+    use super::*;
+    use crate::test_support::*;
+
+    // Test rig {{{3
+    macro_rules! test_rig {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (area, wgt, hgt, hp, kind, set_area) = $value;
+
+                    // HMS Warrior: 380 ft x 58 ft
+                    let rig = Rig { kind, area: set_area };
+
+                    assert_eq!(area, to_place(rig.sail_area(380.0, 58.0), 0));
+                    assert_eq!(wgt, to_place(rig.wgt(380.0, 58.0), 1));
+                    assert_eq!(hgt, to_place(rig.hgt(380.0, 58.0), 1));
+                    assert_eq!(hp, to_place(rig.hp(380.0, 58.0), 0));
+                }
+            )*
+        }
+    }
+    test_rig! {
+        // name:        (area, wgt, hgt, hp, kind, area set)
+        rig_none:       (0.0, 0.0, 0.0, 0.0, RigType::None, 0.0),
+        rig_none_area:  (0.0, 0.0, 0.0, 0.0, RigType::None, 10000.0),
+        rig_auxiliary:  (17632.0, 88.2, 26.6, 1411.0, RigType::Auxiliary, 0.0),
+        rig_barque:     (35264.0, 176.3, 37.6, 3174.0, RigType::Barque, 0.0),
+        rig_full:       (48488.0, 242.4, 44.0, 4849.0, RigType::FullRigged, 0.0),
+        rig_full_area:  (40000.0, 200.0, 40.0, 4000.0, RigType::FullRigged, 40000.0),
+    }
+}
@@ -0,0 +1,444 @@
+use crate::Ship;
+
+use format_num::format_num;
+
+use std::fmt;
+use std::str::FromStr;
+
+// ShipCategory {{{1
+/// Treaty categories of warship.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ShipCategory {
+    CapitalShip,
+    HeavyCruiser,
+    LightCruiser,
+    Destroyer,
+}
+
+impl fmt::Display for ShipCategory { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::CapitalShip  => "capital ship",
+                Self::HeavyCruiser => "heavy cruiser",
+                Self::LightCruiser => "light cruiser",
+                Self::Destroyer    => "destroyer",
+            }
+        )
+    }
+}
+
+// Limit {{{1
+/// Treaty limits for one category of warship.
+///
+#[derive(Clone, Debug)]
+pub struct Limit {
+    /// Category limited.
+    pub category: ShipCategory,
+    /// Maximum standard displacement of a single ship (t).
+    pub d_std: f64,
+    /// Maximum gun calibre (in).
+    pub gun: f64,
+    /// Total standard displacement allowed in the category for each
+    /// signatory (t).
+    pub tonnage: Vec<(Nation, f64)>,
+}
+
+// Nation {{{1
+/// Treaty signatories with tonnage allotments.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Nation {
+    BritishEmpire,
+    UnitedStates,
+    Japan,
+    France,
+    Italy,
+    Germany,
+}
+
+impl fmt::Display for Nation { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::BritishEmpire => "British Empire",
+                Self::UnitedStates  => "United States",
+                Self::Japan         => "Japan",
+                Self::France        => "France",
+                Self::Italy         => "Italy",
+                Self::Germany       => "Germany",
+            }
+        )
+    }
+}
+
+impl Nation { // {{{2
+    // from_country {{{3
+    /// Find the signatory matching a ship's country, if any.
+    ///
+    pub fn from_country(country: &str) -> Option<Self> {
+        let country = country.to_lowercase();
+
+        let names: [(Self, &[&str]); 6] = [
+            (Self::BritishEmpire, &["brit", "uk", "united kingdom", "england", "royal navy"]),
+            (Self::UnitedStates,  &["united states", "usa", "us", "america"]),
+            (Self::Japan,         &["japan", "ijn"]),
+            (Self::France,        &["fran"]),
+            (Self::Italy,         &["ital"]),
+            (Self::Germany,       &["germ", "reich"]),
+        ];
+
+        names.iter()
+            .find(|(_, aliases)| aliases.iter().any(|a|
+                if a.len() <= 3 { country == *a } else { country.contains(a) }
+            ))
+            .map(|(nation, _)| *nation)
+    }
+}
+
+// Testing Nation {{{2
+#[cfg(test)]
+mod nation {
+    use super::*;
+
+    // Test from_country {{{3
+    macro_rules! test_from_country {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, country) = $value;
+
+                    assert_eq!(expected, Nation::from_country(country));
+                }
+            )*
+        }
+    }
+    test_from_country! {
+        // name:                   (nation, country)
+        from_country_britain:      (Some(Nation::BritishEmpire), "Great Britain"),
+        from_country_uk:           (Some(Nation::BritishEmpire), "UK"),
+        from_country_us:           (Some(Nation::UnitedStates), "US"),
+        from_country_us_long:      (Some(Nation::UnitedStates), "United States of America"),
+        from_country_germany:      (Some(Nation::Germany), "Germany"),
+        from_country_russia:       (None, "Russia"),
+        from_country_no_substring: (None, "Mauritius"),
+    }
+}
+
+// Clause {{{1
+/// Result of checking a ship against one clause of a treaty.
+///
+#[derive(Clone, Debug)]
+pub struct Clause {
+    /// Description of the clause as applied to the ship.
+    pub text: String,
+    /// If the ship complies with the clause.
+    pub pass: bool,
+}
+
+// Treaty {{{1
+/// Naval arms limitation treaties.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Treaty {
+    Washington1922,
+    London1930,
+    AngloGerman1935,
+    London1936,
+}
+
+impl FromStr for Treaty { // {{{2
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "washington1922"  => Ok(Self::Washington1922),
+            "london1930"      => Ok(Self::London1930),
+            "anglogerman1935" => Ok(Self::AngloGerman1935),
+            "london1936"      => Ok(Self::London1936),
+            _ => Err(format!(
+                "Unknown treaty '{}': use one of washington1922, london1930, anglogerman1935, london1936", s
+            )),
+        }
+    }
+}
+
+impl fmt::Display for Treaty { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Washington1922  => "Washington Naval Treaty (1922)",
+                Self::London1930      => "London Naval Treaty (1930)",
+                Self::AngloGerman1935 => "Anglo-German Naval Agreement (1935)",
+                Self::London1936      => "Second London Naval Treaty (1936)",
+            }
+        )
+    }
+}
+
+impl Treaty { // {{{2
+    // bounds {{{3
+    /// Largest standard displacement (t) and gun calibre (in) of each
+    /// category of warship, smallest category first. Anything larger is a
+    /// capital ship.
+    ///
+    /// The Washington Treaty only distinguished capital ships, so the
+    /// sub-categories of the London Treaty are used for it.
+    ///
+    pub fn bounds(&self) -> Vec<(ShipCategory, f64, f64)> {
+        use ShipCategory::*;
+
+        match self {
+            Self::Washington1922 | Self::London1930 | Self::AngloGerman1935 => vec![
+                (Destroyer,    1_850.0,  5.1),
+                (LightCruiser, 10_000.0, 6.1),
+                (HeavyCruiser, 10_000.0, 8.0),
+            ],
+
+            // Light surface vessels of up to 3,000 t are destroyers
+            Self::London1936 => vec![
+                (Destroyer,    3_000.0,  6.1),
+                (LightCruiser, 10_000.0, 6.1),
+                (HeavyCruiser, 10_000.0, 8.0),
+            ],
+        }
+    }
+
+    // classify {{{3
+    /// Classify a ship by standard displacement (t) and largest gun calibre
+    /// (in) using the definitions of the treaty.
+    ///
+    pub fn classify(&self, d_std: f64, gun: f64) -> ShipCategory {
+        self.bounds().into_iter()
+            .find(|(_, d, g)| d_std <= *d && gun <= *g)
+            .map(|(category, _, _)| category)
+            .unwrap_or(ShipCategory::CapitalShip)
+    }
+
+    // limits {{{3
+    /// Limits for each category of warship. Categories that are not listed
+    /// may not be built under the treaty.
+    ///
+    pub fn limits(&self) -> Vec<Limit> {
+        use Nation::*;
+        use ShipCategory::*;
+
+        let capital = vec![
+            (UnitedStates, 525_000.0), (BritishEmpire, 525_000.0), (Japan, 315_000.0),
+            (France, 175_000.0), (Italy, 175_000.0),
+        ];
+
+        match self {
+            // Cruisers and destroyers are only limited to 10,000 t and 8"
+            Self::Washington1922 => vec![
+                Limit { category: CapitalShip,  d_std: 35_000.0, gun: 16.0, tonnage: capital },
+                Limit { category: HeavyCruiser, d_std: 10_000.0, gun: 8.0,  tonnage: vec![] },
+                Limit { category: LightCruiser, d_std: 10_000.0, gun: 8.0,  tonnage: vec![] },
+                Limit { category: Destroyer,    d_std: 10_000.0, gun: 8.0,  tonnage: vec![] },
+            ],
+
+            Self::London1930 => vec![
+                Limit { category: CapitalShip,  d_std: 35_000.0, gun: 16.0, tonnage: capital },
+                Limit { category: HeavyCruiser, d_std: 10_000.0, gun: 8.0,
+                    tonnage: vec![(UnitedStates, 180_000.0), (BritishEmpire, 146_800.0), (Japan, 108_400.0)] },
+                Limit { category: LightCruiser, d_std: 10_000.0, gun: 6.1,
+                    tonnage: vec![(UnitedStates, 143_500.0), (BritishEmpire, 192_200.0), (Japan, 100_450.0)] },
+                Limit { category: Destroyer,    d_std: 1_850.0,  gun: 5.1,
+                    tonnage: vec![(UnitedStates, 150_000.0), (BritishEmpire, 150_000.0), (Japan, 105_500.0)] },
+            ],
+
+            // Germany is allowed 35% of British tonnage in each category
+            Self::AngloGerman1935 => vec![
+                Limit { category: CapitalShip,  d_std: 35_000.0, gun: 16.0, tonnage: vec![(Germany, 183_750.0)] },
+                Limit { category: HeavyCruiser, d_std: 10_000.0, gun: 8.0,  tonnage: vec![(Germany, 51_380.0)] },
+                Limit { category: LightCruiser, d_std: 10_000.0, gun: 6.1,  tonnage: vec![(Germany, 67_270.0)] },
+                Limit { category: Destroyer,    d_std: 1_850.0,  gun: 5.1,  tonnage: vec![(Germany, 52_500.0)] },
+            ],
+
+            // No new heavy cruisers and no quantitative limits
+            Self::London1936 => vec![
+                Limit { category: CapitalShip,  d_std: 35_000.0, gun: 14.0, tonnage: vec![] },
+                Limit { category: LightCruiser, d_std: 8_000.0,  gun: 6.1,  tonnage: vec![] },
+                Limit { category: Destroyer,    d_std: 3_000.0,  gun: 6.1,  tonnage: vec![] },
+            ],
+        }
+    }
+
+    // check {{{3
+    /// Check a ship against each clause of the treaty that applies to it.
+    ///
+    pub fn check(&self, ship: &Ship) -> Vec<Clause> {
+        let d_std = ship.d_std();
+        let gun = ship.gun_max();
+        let category = self.classify(d_std, gun);
+
+        let limit = match self.limits().into_iter().find(|l| l.category == category) {
+            Some(limit) => limit,
+            None => return vec![Clause {
+                text: format!("No {} may be built", category),
+                pass: false,
+            }],
+        };
+
+        let clauses = vec![
+            Clause {
+                text: format!("Standard displacement of {} t, limit {} t",
+                    format_num!(",.0", d_std), format_num!(",.0", limit.d_std)),
+                pass: d_std <= limit.d_std,
+            },
+            Clause {
+                text: format!("Largest gun calibre of {:.2}\", limit {:.2}\"",
+                    gun, limit.gun),
+                pass: gun <= limit.gun,
+            },
+        ];
+
+        clauses
+    }
+
+    // tonnage {{{3
+    /// Share of the national tonnage allotment for its category used by the
+    /// ship. This is for information only as compliance depends on the rest
+    /// of the fleet.
+    ///
+    pub fn tonnage(&self, ship: &Ship) -> Option<String> {
+        let d_std = ship.d_std();
+        let category = self.classify(d_std, ship.gun_max());
+        let nation = Nation::from_country(&ship.country)?;
+
+        let limit = self.limits().into_iter().find(|l| l.category == category)?;
+        let (_, tonnage) = limit.tonnage.iter().find(|(n, _)| *n == nation)?;
+
+        Some(format!("Uses {:.1} % of the {} {} tonnage of {} t",
+            d_std / tonnage * 100.0, nation, category, format_num!(",.0", *tonnage)))
+    }
+
+    // report {{{3
+    /// Print check() with the ship's classification.
+    ///
+    pub fn report(&self, ship: &Ship) -> String {
+        let mut r: Vec<String> = Vec::new();
+        let clauses = self.check(ship);
+
+        r.push(format!("{}: {}", ship.name, self));
+        r.push(format!("Classified as a {}",
+            self.classify(ship.d_std(), ship.gun_max())));
+
+        for c in clauses.iter() {
+            r.push(format!("    {}  {}", if c.pass { "PASS" } else { "FAIL" }, c.text));
+        }
+
+        if let Some(tonnage) = self.tonnage(ship) {
+            r.push(format!("    INFO  {}", tonnage));
+        }
+
+        r.push(if clauses.iter().all(|c| c.pass) {
+            "Ship complies with the treaty".into()
+        } else {
+            "Ship does not comply with the treaty".into()
+        });
+
+        r.join("\n")
+    }
+}
+
+// Testing Treaty {{{2
+#[cfg(test)]
+mod treaty {
+    use super::*;
+
+    // Test from_str {{{3
+    macro_rules! test_from_str {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, s) = $value;
+
+                    assert_eq!(expected, s.parse::<Treaty>().ok());
+                }
+            )*
+        }
+    }
+    test_from_str! {
+        // name:                   (treaty, string)
+        from_str_washington:       (Some(Treaty::Washington1922), "washington1922"),
+        from_str_london:           (Some(Treaty::London1930), "London1930"),
+        from_str_anglo_german:     (Some(Treaty::AngloGerman1935), "anglogerman1935"),
+        from_str_second_london:    (Some(Treaty::London1936), "london1936"),
+        from_str_unknown:          (None, "versailles1919"),
+    }
+
+    // Test classify {{{3
+    macro_rules! test_classify {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, treaty, d_std, gun) = $value;
+
+                    let treaty: Treaty = treaty;
+                    assert_eq!(expected, treaty.classify(d_std, gun));
+                }
+            )*
+        }
+    }
+    test_classify! {
+        // name:                  (category, treaty, d_std, gun)
+        classify_capital_d:       (ShipCategory::CapitalShip, Treaty::London1930, 10_001.0, 6.0),
+        classify_capital_gun:     (ShipCategory::CapitalShip, Treaty::London1930, 8_000.0, 11.0),
+        classify_heavy:           (ShipCategory::HeavyCruiser, Treaty::London1930, 10_000.0, 8.0),
+        classify_light:           (ShipCategory::LightCruiser, Treaty::London1930, 9_000.0, 6.1),
+        classify_light_small_gun: (ShipCategory::LightCruiser, Treaty::London1930, 2_000.0, 4.7),
+        classify_light_big_gun:   (ShipCategory::LightCruiser, Treaty::London1930, 1_500.0, 5.5),
+        classify_destroyer:       (ShipCategory::Destroyer, Treaty::London1930, 1_850.0, 5.1),
+        classify_washington:      (ShipCategory::Destroyer, Treaty::Washington1922, 1_500.0, 4.7),
+        classify_1936_destroyer:  (ShipCategory::Destroyer, Treaty::London1936, 2_500.0, 5.0),
+        classify_1936_light:      (ShipCategory::LightCruiser, Treaty::London1936, 3_001.0, 6.0),
+        classify_1936_heavy:      (ShipCategory::HeavyCruiser, Treaty::London1936, 2_500.0, 8.0),
+    }
+
+    // Test check {{{3
+    macro_rules! test_check {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, tonnage, treaty, country, d, gun) = $value;
+
+                    let mut ship = Ship::default();
+                    ship.country = String::from(country);
+                    ship.hull.set_d(d);
+                    ship.hull.set_lwl(d / 15.0);
+                    ship.hull.b = 40.0;
+                    ship.hull.bb = 40.0;
+                    ship.hull.t = 15.0;
+                    ship.add_battery();
+                    ship.batteries[0].num = 8;
+                    ship.batteries[0].diam = gun;
+
+                    let treaty: Treaty = treaty;
+                    let result: Vec<bool> = treaty.check(&ship).iter().map(|c| c.pass).collect();
+
+                    assert_eq!(expected, result);
+                    assert_eq!(tonnage, treaty.tonnage(&ship).is_some());
+                }
+            )*
+        }
+    }
+    test_check! {
+        // name:                    (passes, tonnage, treaty, country, d_std, gun)
+        check_treaty_cruiser:       (vec![true, true], false, Treaty::Washington1922, "Italy", 9_000.0, 8.0),
+        check_treaty_cruiser_limit: (vec![true, true], false, Treaty::London1930, "Italy", 10_000.0, 8.0),
+        check_light_cruiser_big:    (vec![false, true], false, Treaty::London1936, "UK", 9_000.0, 6.0),
+        check_light_cruiser_guns:   (vec![true, true], true, Treaty::London1930, "UK", 7_000.0, 6.0),
+        check_heavy_cruiser_guns:   (vec![true, true], true, Treaty::London1930, "USA", 7_000.0, 8.0),
+        check_capital_tonnage:      (vec![true, true], true, Treaty::Washington1922, "Japan", 30_000.0, 16.0),
+        check_capital_big_gun:      (vec![true, false], false, Treaty::London1936, "France", 30_000.0, 16.0),
+        check_no_heavy_cruisers:    (vec![false], false, Treaty::London1936, "UK", 7_000.0, 8.0),
+        check_germany:              (vec![true, true], true, Treaty::AngloGerman1935, "Germany", 1_500.0, 5.0),
+        check_1936_destroyer:       (vec![true, true], false, Treaty::London1936, "USA", 2_500.0, 5.0),
+    }
+}
@@ -0,0 +1,287 @@
+use serde::Serialize;
+
+// Section {{{1
+/// Box shaped midship section used to find how far the centre of buoyancy
+/// moves as the ship heels, including past the point where the deck edge
+/// goes under.
+///
+#[derive(Clone, Debug, Default)]
+pub struct Section {
+    /// Beam at the waterline (ft).
+    pub beam: f64,
+    /// Height of the deck above the keel (ft).
+    pub depth: f64,
+    /// Draft (ft).
+    pub draft: f64,
+}
+
+impl Section { // {{{2
+    /// Number of halvings used to find the heeled waterline.
+    ///
+    const ITERATIONS: u32 = 60;
+
+    // corners {{{3
+    /// Corners of the section, anticlockwise from the keel to port, as
+    /// (distance to starboard, height above keel).
+    ///
+    fn corners(&self) -> Vec<(f64, f64)> {
+        let y = self.beam / 2.0;
+
+        vec![(-y, 0.0), (y, 0.0), (y, self.depth), (-y, self.depth)]
+    }
+
+    // submerged {{{3
+    /// Part of the section below a waterline heeled to starboard at a given
+    /// angle (degrees) and at a given height along the upright direction of
+    /// the heeled waterline.
+    ///
+    fn submerged(&self, heel: f64, c: f64) -> Vec<(f64, f64)> {
+        let (sin, cos) = heel.to_radians().sin_cos();
+        let height = |(y, z): (f64, f64)| z * cos - y * sin;
+
+        // Clip the section to the water
+        let corners = self.corners();
+        let mut poly = Vec::new();
+        for (i, &p) in corners.iter().enumerate() {
+            let q = corners[(i + 1) % corners.len()];
+            let (hp, hq) = (height(p), height(q));
+
+            if hp <= c { poly.push(p); }
+            if (hp < c) != (hq < c) && hp != hq {
+                let f = (c - hp) / (hq - hp);
+                poly.push((p.0 + f * (q.0 - p.0), p.1 + f * (q.1 - p.1)));
+            }
+        }
+
+        poly
+    }
+
+    // area {{{3
+    /// Area and centroid of a polygon.
+    ///
+    fn area(poly: &[(f64, f64)]) -> (f64, (f64, f64)) {
+        let (mut a, mut cy, mut cz) = (0.0, 0.0, 0.0);
+
+        for (i, &(y0, z0)) in poly.iter().enumerate() {
+            let (y1, z1) = poly[(i + 1) % poly.len()];
+            let cross = y0 * z1 - y1 * z0;
+
+            a  += cross;
+            cy += (y0 + y1) * cross;
+            cz += (z0 + z1) * cross;
+        }
+
+        if a == 0.0 { return (0.0, (0.0, 0.0)); } // Catch divide by zero
+
+        (a / 2.0, (cy / (3.0 * a), cz / (3.0 * a)))
+    }
+
+    // km {{{3
+    /// Height of the transverse metacentre above the keel when upright.
+    ///
+    pub fn km(&self) -> f64 {
+        if self.draft <= 0.0 { return 0.0; } // Catch divide by zero
+
+        self.draft / 2.0 + self.beam.powf(2.0) / (12.0 * self.draft)
+    }
+
+    // kn {{{3
+    /// Horizontal distance from the keel to the centre of buoyancy when
+    /// heeled to a given angle (degrees).
+    ///
+    pub fn kn(&self, heel: f64) -> f64 {
+        let target = self.beam * self.draft.min(self.depth);
+        if target <= 0.0 { return 0.0; }
+
+        // The submerged area grows with the height of the waterline
+        let (sin, cos) = heel.to_radians().sin_cos();
+        let heights: Vec<f64> = self.corners().iter().map(|(y, z)| z * cos - y * sin).collect();
+        let (mut lo, mut hi) = (
+            heights.iter().cloned().fold(f64::MAX, f64::min),
+            heights.iter().cloned().fold(f64::MIN, f64::max),
+        );
+        for _ in 0..Self::ITERATIONS {
+            let c = (lo + hi) / 2.0;
+            if Self::area(&self.submerged(heel, c)).0 < target { lo = c; } else { hi = c; }
+        }
+
+        let (_, (y, z)) = Self::area(&self.submerged(heel, (lo + hi) / 2.0));
+
+        y * cos + z * sin
+    }
+}
+
+// Testing Section {{{2
+#[cfg(test)]
+mod section {
+    use super::*;
+    use crate::test_support::*;
+
+    // Test kn {{{3
+    macro_rules! test_kn {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, heel) = $value;
+
+                    let section = Section { beam: 40.0, depth: 20.0, draft: 10.0 };
+
+                    assert_eq!(expected, to_place(section.kn(heel), 2));
+                }
+            )*
+        }
+    }
+    test_kn! {
+        // name:          (kn, heel)
+        kn_upright:       (0.0, 0.0),
+        // Wall sided: KN = sin (KB + BM (1 + tan² / 2))
+        kn_wall_sided:    (3.22, 10.0),
+        kn_deck_edge:     (10.05, 30.0),
+        kn_beam_ends:     (10.0, 90.0),
+        kn_capsized:      (0.0, 180.0),
+    }
+
+    // Test km {{{3
+    #[test]
+    fn km() {
+        let section = Section { beam: 40.0, depth: 20.0, draft: 10.0 };
+        assert_eq!(18.33, to_place(section.km(), 2));
+
+        let section = Section { beam: 40.0, depth: 20.0, draft: 0.0 };
+        assert_eq!(0.0, section.km());
+    }
+}
+
+// GzPoint {{{1
+/// Righting arm at one angle of heel.
+///
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct GzPoint {
+    /// Angle of heel (degrees).
+    pub heel: f64,
+    /// Righting arm (ft).
+    pub gz: f64,
+}
+
+// GzCurve {{{1
+/// Righting arm curve of a ship in one loading condition, from upright to
+/// the angle of vanishing stability.
+///
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct GzCurve {
+    /// Name of the loading condition.
+    pub label: String,
+    /// Height of the centre of gravity above the keel (ft).
+    pub kg: f64,
+    /// Metacentric height (ft).
+    pub gm: f64,
+    /// Righting arms, ending at the angle of vanishing stability.
+    pub points: Vec<GzPoint>,
+}
+
+impl GzCurve { // {{{2
+    /// Interval between angles of heel (degrees).
+    ///
+    pub const STEP: f64 = 1.0;
+
+    /// Largest angle of heel (degrees).
+    ///
+    const HEEL_MAX: f64 = 180.0;
+
+    // new {{{3
+    /// Righting arm curve of a section whose upright metacentre is at `km`
+    /// and centre of gravity is at `kg` above the keel.
+    ///
+    /// The movement of the centre of buoyancy of the box section is scaled
+    /// so that it has the same metacentre as the hull when upright.
+    ///
+    pub fn new(label: String, section: &Section, km: f64, kg: f64) -> Self {
+        let scale = if section.km() > 0.0 { km / section.km() } else { 0.0 };
+        let gz = |heel: f64| scale * section.kn(heel) - kg * heel.to_radians().sin();
+
+        let mut points = vec![GzPoint { heel: 0.0, gz: 0.0 }];
+        let mut heel = Self::STEP;
+        while heel <= Self::HEEL_MAX {
+            let p = GzPoint { heel, gz: gz(heel) };
+
+            if p.gz <= 0.0 {
+                // End the curve where the righting arm vanishes
+                let last = points.last().unwrap();
+                let heel = if last.gz > 0.0 {
+                    last.heel + Self::STEP * last.gz / (last.gz - p.gz)
+                } else {
+                    0.0
+                };
+                if heel > 0.0 { points.push(GzPoint { heel, gz: 0.0 }); }
+                break;
+            }
+
+            points.push(p);
+            heel += Self::STEP;
+        }
+
+        Self { label, kg, gm: km - kg, points }
+    }
+
+    // range {{{3
+    /// Angle of vanishing stability (degrees).
+    ///
+    pub fn range(&self) -> f64 {
+        match self.points.last() {
+            Some(p) => p.heel,
+            None => 0.0,
+        }
+    }
+
+    // max {{{3
+    /// Largest righting arm and the angle it is reached at.
+    ///
+    pub fn max(&self) -> GzPoint {
+        self.points.iter()
+            .fold(GzPoint::default(), |max, p| if p.gz > max.gz { p.clone() } else { max })
+    }
+}
+
+// Testing GzCurve {{{2
+#[cfg(test)]
+mod gz_curve {
+    use super::*;
+    use crate::test_support::*;
+
+    // Test new {{{3
+    macro_rules! test_new {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (range, max_heel, max_gz, kg) = $value;
+
+                    let section = Section { beam: 40.0, depth: 20.0, draft: 10.0 };
+                    let curve = GzCurve::new("Test".into(), &section, section.km(), kg);
+
+                    assert_eq!(range, to_place(curve.range(), 1));
+                    assert_eq!((max_heel, max_gz), (curve.max().heel, to_place(curve.max().gz, 2)));
+                    assert_eq!(to_place(section.km() - kg, 6), to_place(curve.gm, 6));
+                }
+            )*
+        }
+    }
+    test_new! {
+        // name:       (range, max heel, max gz, kg)
+        gz_low:        (148.7, 56.0, 12.74, 0.0),
+        gz_typical:    (63.8, 34.0, 3.15, 14.0),
+        gz_high:       (47.5, 31.0, 1.56, 17.0),
+        gz_unstable:   (0.0, 0.0, 0.0, 20.0),
+    }
+
+    // Test scale {{{3
+    #[test]
+    fn scale() {
+        let section = Section { beam: 40.0, depth: 20.0, draft: 10.0 };
+
+        // Initial slope follows the metacentric height of the hull
+        let curve = GzCurve::new("Test".into(), &section, 16.0, 14.0);
+        assert_eq!(0.03, to_place(curve.points[1].gz, 2));
+    }
+}
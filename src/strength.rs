@@ -0,0 +1,278 @@
+use crate::Hull;
+
+use serde::Serialize;
+
+use std::f64::consts::PI;
+use std::fmt;
+
+// Wave {{{1
+/// Sea the hull girder is balanced on.
+///
+#[derive(Serialize, Clone, Copy, Debug, PartialEq)]
+pub enum Wave {
+    /// Calm water.
+    Still,
+    /// Wave crest amidships and troughs at the ends.
+    Hogging,
+    /// Wave troughs amidships and crests at the ends.
+    Sagging,
+}
+
+impl Wave { // {{{2
+    /// Every sea, calm first.
+    ///
+    pub const ALL: [Wave; 3] = [Wave::Still, Wave::Hogging, Wave::Sagging];
+
+    // crest {{{3
+    /// Height of the wave amidships as a fraction of its height from
+    /// trough to crest.
+    ///
+    fn crest(&self) -> f64 {
+        match self {
+            Self::Still   => 0.0,
+            Self::Hogging => 0.5,
+            Self::Sagging => -0.5,
+        }
+    }
+}
+
+impl fmt::Display for Wave { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Still   => "Still water",
+                Self::Hogging => "Hogging",
+                Self::Sagging => "Sagging",
+            }
+        )
+    }
+}
+
+// StrengthPoint {{{1
+/// Loads on the hull girder at one point along its length.
+///
+#[derive(Serialize, Clone, Debug, Default, PartialEq)]
+pub struct StrengthPoint {
+    /// Distance from the bow (ft).
+    pub x: f64,
+    /// Weight per foot of the station ending here (tons/ft).
+    pub weight: f64,
+    /// Buoyancy per foot of the station ending here (tons/ft).
+    pub buoyancy: f64,
+    /// Shear force (tons).
+    pub shear: f64,
+    /// Bending moment (ft-tons), positive hogging.
+    pub moment: f64,
+}
+
+// Strength {{{1
+/// Shear force and bending moment along the hull girder in one sea.
+///
+#[derive(Serialize, Clone, Debug)]
+pub struct Strength {
+    /// Sea the girder is balanced on.
+    pub wave: Wave,
+    /// Loads from bow to stern.
+    pub points: Vec<StrengthPoint>,
+}
+
+impl Strength { // {{{2
+    // max_moment {{{3
+    /// Largest bending moment (ft-tons), positive hogging.
+    ///
+    pub fn max_moment(&self) -> f64 {
+        self.points.iter()
+            .fold(0.0, |max: f64, p| if p.moment.abs() > max.abs() { p.moment } else { max })
+    }
+
+    // max_shear {{{3
+    /// Largest shear force (tons), either way.
+    ///
+    pub fn max_shear(&self) -> f64 {
+        self.points.iter().fold(0.0, |max: f64, p| max.max(p.shear.abs()))
+    }
+}
+
+// Girder {{{1
+/// Hull treated as a beam loaded by its weights and held up by the water.
+///
+/// Weights are (tons, distance forward of midships, length spread over)
+/// with distances in feet. A weight spread over the whole length varies
+/// evenly from end to end so that its centre stays where it is; shorter
+/// ones are spread evenly.
+///
+#[derive(Clone, Debug, Default)]
+pub struct Girder {
+    /// Waterline length (ft).
+    pub lwl: f64,
+    /// Prismatic coefficient, the fullness of the underwater ends.
+    pub cp: f64,
+    /// Waterplane coefficient, the fullness of the waterline.
+    pub cwp: f64,
+    /// Maximum beam at the waterline (ft).
+    pub bb: f64,
+    /// Weights along the length.
+    pub weights: Vec<(f64, f64, f64)>,
+}
+
+impl Girder { // {{{2
+    /// Number of stations along the length.
+    ///
+    const STATIONS: usize = 50;
+
+    // shape {{{3
+    /// Fullness of a curve that is 1 amidships and falls to 0 at the ends,
+    /// at `u` from midships as a fraction of half the length, for a curve
+    /// whose mean is `c`.
+    ///
+    fn shape(c: f64, u: f64) -> f64 {
+        let c = c.clamp(0.01, 0.99);
+
+        1.0 - u.abs().powf(c / (1.0 - c))
+    }
+
+    // weight {{{3
+    /// Weight per foot between two points forward of midships.
+    ///
+    fn weight(&self, fwd: f64, aft: f64) -> f64 {
+        let half = self.lwl / 2.0;
+        let mid = (fwd + aft) / 2.0;
+
+        self.weights.iter()
+            .map(|&(w, x, len)| {
+                if len >= self.lwl {
+                    // Keep the weight positive at both ends
+                    let slope = (12.0 * x / self.lwl.powf(2.0)).clamp(-1.0 / half, 1.0 / half);
+                    w / self.lwl * (1.0 + slope * mid)
+                } else {
+                    let x = x.clamp(len / 2.0 - half, half - len / 2.0);
+                    let overlap = (fwd.min(x + len / 2.0) - aft.max(x - len / 2.0)).max(0.0);
+
+                    if len > 0.0 {
+                        w / len * overlap / (fwd - aft)
+                    } else if (aft..fwd).contains(&x) {
+                        w / (fwd - aft)
+                    } else {
+                        0.0
+                    }
+                }
+            })
+            .sum()
+    }
+
+    // strength {{{3
+    /// Shear force and bending moment with the hull balanced on a wave of
+    /// a given height from trough to crest (ft), as long as the hull.
+    ///
+    pub fn strength(&self, wave: Wave, hgt: f64) -> Strength {
+        if self.lwl <= 0.0 { return Strength { wave, points: Vec::new() }; } // Catch divide by zero
+
+        let dx = self.lwl / Self::STATIONS as f64;
+        let half = self.lwl / 2.0;
+
+        // Distance forward of midships of the centre of each station
+        let xs: Vec<f64> = (0..Self::STATIONS).map(|i| half - (i as f64 + 0.5) * dx).collect();
+        let sum = |f: &dyn Fn(usize) -> f64| (0..Self::STATIONS).fold(0.0, |s, i| s + f(i) * dx);
+
+        let weight: Vec<f64> = xs.iter().map(|x| self.weight(x + dx / 2.0, x - dx / 2.0)).collect();
+        let breadth: Vec<f64> = xs.iter().map(|x| self.bb * Self::shape(self.cwp, x / half)).collect();
+        let area: Vec<f64> = xs.iter().map(|x| Self::shape(self.cp, x / half)).collect();
+
+        // Buoyancy of the underwater body carries the whole weight
+        let total = sum(&|i| weight[i]);
+        let scale = total / sum(&|i| area[i]);
+        let mut buoyancy: Vec<f64> = area.iter().map(|a| a * scale).collect();
+
+        // The wave adds buoyancy under the crest and the ship sinks or
+        // rises until it again carries the same weight
+        for (i, x) in xs.iter().enumerate() {
+            let eta = wave.crest() * hgt * (2.0 * PI * x / self.lwl).cos();
+            buoyancy[i] += breadth[i] * eta / Hull::FT3_PER_TON_SEA;
+        }
+        let sinkage = (total - sum(&|i| buoyancy[i])) / sum(&|i| breadth[i]);
+        for (b, y) in buoyancy.iter_mut().zip(breadth.iter()) {
+            *b += y * sinkage;
+        }
+
+        // Trim until the centre of buoyancy is under the centre of gravity
+        let moment = sum(&|i| (weight[i] - buoyancy[i]) * xs[i]);
+        let trim = moment / sum(&|i| breadth[i] * xs[i].powf(2.0));
+        for ((b, y), x) in buoyancy.iter_mut().zip(breadth.iter()).zip(xs.iter()) {
+            *b += y * x * trim;
+        }
+
+        // Integrate from the bow
+        let mut points = vec![StrengthPoint::default()];
+        let (mut shear, mut moment) = (0.0, 0.0);
+        for i in 0..Self::STATIONS {
+            let prev = shear;
+            shear += (weight[i] - buoyancy[i]) * dx;
+            moment += (prev + shear) / 2.0 * dx;
+
+            points.push(StrengthPoint {
+                x: (i + 1) as f64 * dx,
+                weight: weight[i],
+                buoyancy: buoyancy[i],
+                shear,
+                moment,
+            });
+        }
+
+        Strength { wave, points }
+    }
+}
+
+// Testing Girder {{{2
+#[cfg(test)]
+mod girder {
+    use super::*;
+    use crate::test_support::*;
+
+    fn get_girder(weights: Vec<(f64, f64, f64)>) -> Girder {
+        Girder { lwl: 500.0, cp: 0.6, cwp: 0.7, bb: 50.0, weights }
+    }
+
+    // Test strength {{{3
+    macro_rules! test_strength {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, wave, weights) = $value;
+
+                    let girder = get_girder(weights);
+                    let strength = girder.strength(wave, 25.0);
+
+                    assert_eq!(expected, to_place(strength.max_moment() / 1000.0, 0));
+
+                    // Balanced: no shear or moment left at the stern
+                    let last = strength.points.last().unwrap();
+                    assert!(last.shear.abs() < 1e-6 && last.moment.abs() < 1e-3, "{:?}", last);
+                }
+            )*
+        }
+    }
+    test_strength! {
+        // name:            (max moment (1000 ft-tons), wave, weights)
+        // Uniform weight: about W L / 28 for a prismatic coefficient of 0.6
+        strength_even:      (125.0, Wave::Still, vec![(7000.0, 0.0, 500.0)]),
+        strength_ends:      (256.0, Wave::Still, vec![(3500.0, 0.0, 500.0), (1750.0, 200.0, 50.0), (1750.0, -200.0, 50.0)]),
+        strength_middle:    (-50.0, Wave::Still, vec![(3500.0, 0.0, 500.0), (3500.0, 0.0, 100.0)]),
+        strength_forward:   (125.0, Wave::Still, vec![(7000.0, 20.0, 500.0)]),
+        strength_point:     (115.0, Wave::Still, vec![(6000.0, 0.0, 500.0), (1000.0, 100.0, 0.0)]),
+        strength_hogging:   (242.0, Wave::Hogging, vec![(7000.0, 0.0, 500.0)]),
+        // The fine ends still hog the hull in the trough of a wave
+        strength_sagging:   (23.0, Wave::Sagging, vec![(7000.0, 0.0, 500.0)]),
+    }
+
+    // Test empty {{{3
+    #[test]
+    fn empty() {
+        let girder = Girder::default();
+        assert!(girder.strength(Wave::Still, 25.0).points.is_empty());
+
+        let strength = get_girder(Vec::new()).strength(Wave::Still, 0.0);
+        assert_eq!(0.0, strength.max_moment());
+        assert_eq!(0.0, strength.max_shear());
+    }
+}
@@ -0,0 +1,191 @@
+use serde::{Serialize, Deserialize};
+
+use std::f64::consts::PI;
+use std::fmt;
+
+// ShipKind {{{1
+/// Whether the ship is designed to fight on the surface or submerged.
+///
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ShipKind {
+    /// Surface warship.
+    #[default]
+    Surface,
+    /// Submersible with a pressure hull.
+    Submarine,
+}
+
+impl fmt::Display for ShipKind { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Surface   => "Surface ship",
+                Self::Submarine => "Submarine",
+            }
+        )
+    }
+}
+
+// PressureHull {{{1
+/// Pressure hull, battery and electric motors of a submarine.
+///
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct PressureHull {
+    /// Diameter (ft), or 0 for the usual share of the beam.
+    pub diam: f64,
+    /// Length as a fraction of waterline length, or 0 for the usual
+    /// length.
+    pub len: f64,
+    /// Weight of the battery (t).
+    pub battery: f64,
+    /// Power of the electric motors (hp).
+    pub motor_hp: f64,
+}
+
+impl PressureHull { // {{{2
+    /// Usual diameter as a fraction of beam.
+    ///
+    const DIAM: f64 = 0.85;
+
+    /// Usual length as a fraction of waterline length.
+    ///
+    const LEN: f64 = 0.75;
+
+    /// Share of the hull weight that goes into the pressure hull.
+    ///
+    const HULL_SHARE: f64 = 0.5;
+
+    /// Weight of plating one inch thick (lbs/sq ft).
+    ///
+    const PLATE_WGT: f64 = 40.8;
+
+    /// Pressure of sea water per foot of depth (psi).
+    ///
+    const PSI_PER_FT: f64 = 0.445;
+
+    /// Weight of electric motors per horsepower (t).
+    ///
+    const MOTOR_WGT: f64 = 0.03;
+
+    /// Share of the volume above the waterline that is watertight, the rest
+    /// being free-flooding casing.
+    ///
+    pub(crate) const TIGHT: f64 = 0.3;
+
+    /// Drag submerged of the casing, conning tower and flood holes as a
+    /// multiple of the friction of a clean hull.
+    ///
+    pub(crate) const DRAG: f64 = 3.8;
+
+    /// Economical submerged speed (kts).
+    ///
+    pub(crate) const ECON_SPEED: f64 = 4.0;
+
+    /// Share of the battery capacity that can be drawn at full power.
+    ///
+    pub(crate) const FULL_RATE: f64 = 0.4;
+
+    // diameter {{{3
+    /// Diameter (ft) of the pressure hull of a ship of a given beam.
+    ///
+    pub fn diameter(&self, bb: f64) -> f64 {
+        if self.diam > 0.0 { self.diam } else { bb * Self::DIAM }
+    }
+
+    // length {{{3
+    /// Length (ft) of the pressure hull of a ship of a given waterline
+    /// length.
+    ///
+    pub fn length(&self, lwl: f64) -> f64 {
+        lwl * if self.len > 0.0 { self.len } else { Self::LEN }
+    }
+
+    // wgt {{{3
+    /// Weight of the battery and motors (t).
+    ///
+    pub fn wgt(&self) -> f64 {
+        self.battery + self.motor_hp * Self::MOTOR_WGT
+    }
+
+    // plating {{{3
+    /// Thickness of the pressure hull plating (in) from the weight of the
+    /// hull structure.
+    ///
+    pub fn plating(&self, wgt_hull: f64, lwl: f64, bb: f64) -> f64 {
+        let area = PI * self.diameter(bb) * self.length(lwl);
+        if area <= 0.0 { return 0.0; } // Catch divide by zero
+
+        (wgt_hull * Self::HULL_SHARE).max(0.0) * 2240.0 / area / Self::PLATE_WGT
+    }
+
+    // stress {{{3
+    /// Working stress of the hull steel of a given year (psi).
+    ///
+    pub fn stress(year: u32) -> f64 {
+        (12_000.0 + (year as f64 - 1915.0) * 1_000.0).clamp(12_000.0, 40_000.0)
+    }
+
+    // test_depth {{{3
+    /// Depth the pressure hull can be taken to (ft), where the hoop stress
+    /// in the plating reaches the working stress.
+    ///
+    pub fn test_depth(&self, year: u32, wgt_hull: f64, lwl: f64, bb: f64) -> f64 {
+        let r = self.diameter(bb) * 12.0 / 2.0;
+        if r <= 0.0 { return 0.0; } // Catch divide by zero
+
+        Self::stress(year) * self.plating(wgt_hull, lwl, bb) / r / Self::PSI_PER_FT
+    }
+
+    // capacity {{{3
+    /// Energy stored in the battery (hp-hours) with the cells of a given
+    /// year.
+    ///
+    pub fn capacity(&self, year: u32) -> f64 {
+        self.battery * (10.0 + (year as f64 - 1900.0) * 0.7).clamp(10.0, 40.0)
+    }
+}
+
+// Testing PressureHull {{{2
+#[cfg(test)]
+mod pressure_hull {
+    use super::*;
+    use crate::test_support::*;
+
+    // Test pressure hull {{{3
+    macro_rules! test_pressure_hull {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (plating, depth, capacity, year, diam, len) = $value;
+
+                    // Type VIIC: 210 ft x 20.3 ft, about 300 tons of hull
+                    let ph = PressureHull { diam, len, battery: 62.0, motor_hp: 750.0 };
+
+                    assert_eq!(plating, to_place(ph.plating(300.0, 210.0, 20.3), 2));
+                    assert_eq!(depth, to_place(ph.test_depth(year, 300.0, 210.0, 20.3), 0));
+                    assert_eq!(capacity, to_place(ph.capacity(year), 0));
+                    assert_eq!(84.5, to_place(ph.wgt(), 1));
+                }
+            )*
+        }
+    }
+    test_pressure_hull! {
+        // name:            (plating, depth, capacity, year, diameter, length)
+        ph_1940:            (0.96, 775.0, 2356.0, 1940, 0.0, 0.0),
+        ph_1910:            (0.96, 251.0, 1054.0, 1910, 0.0, 0.0),
+        ph_1960:            (0.96, 837.0, 2480.0, 1960, 0.0, 0.0),
+        ph_narrow:          (1.08, 973.0, 2356.0, 1940, 15.4, 0.0),
+        ph_short:           (1.45, 1162.0, 2356.0, 1940, 0.0, 0.5),
+    }
+
+    // Test empty {{{3
+    #[test]
+    fn empty() {
+        let ph = PressureHull::default();
+
+        assert_eq!(0.0, ph.plating(300.0, 0.0, 0.0));
+        assert_eq!(0.0, ph.test_depth(1940, 300.0, 0.0, 0.0));
+        assert_eq!(0.0, ph.wgt());
+    }
+}
@@ -42,6 +42,7 @@ pub enum UnitType { // {{{1
     Weight,
     Power, 
     WeightPerArea,
+    Volume,
 }
 
 // Conversion constants {{{2
@@ -50,6 +51,7 @@ const FEET2METERS: f64     = 0.3048;
 const SQFEET2SQMETERS: f64 = 0.092903;
 const POUND2KG: f64        = 0.45359236;
 const HP2KW: f64           = 0.746;
+const CUFEET2CUMETERS: f64 = 0.0283168;
 
 // Functions {{{2
 //
@@ -63,6 +65,7 @@ pub fn metric(imperial: f64, unit_type: UnitType, units: Units) -> f64 { // {{{3
         UnitType::Weight => imperial * POUND2KG,
         UnitType::Power => imperial * HP2KW,
         UnitType::WeightPerArea => imperial / SQFEET2SQMETERS * POUND2KG,
+        UnitType::Volume => imperial * CUFEET2CUMETERS,
     }
 }
 
@@ -1,11 +1,14 @@
-use crate::{Ship, Armor};
+use crate::{Ship, Armor, Gun, Shot};
 use crate::Hull;
 use crate::units::Units;
 
+use format_num::format_num;
+
 use serde::{Serialize, Deserialize};
 
 use std::f64::consts::PI;
 use std::fmt;
+use std::str::FromStr;
 
 // Battery {{{1
 /// A battery of one type of gun.
@@ -28,11 +31,18 @@ pub struct Battery {
 
     /// Number of shells in the magazine
     pub shells: u32,
+    /// Share of the magazine given to each type of shell. An empty
+    /// loadout is all armor piercing.
+    #[serde(default)]
+    pub loadout: Vec<(ShellType, f64)>,
     /// Weight of each shell.
         shell_wgt: Option<f64>,
 
     /// Type of gun.
     pub kind: GunType,
+    /// Maximum elevation of the mounts in degrees.
+    #[serde(default = "Battery::default_elevation")]
+    pub max_elevation: f64,
 
     /// Number of mounts in the battery.
     pub mount_num: u32,
@@ -47,7 +57,8 @@ pub struct Battery {
     /// Armor thickness on barbette.
     pub armor_barb: f64,
 
-    /// Separate groups of guns within the Battery
+    /// Separate groups of guns within the Battery, each with its own
+    /// layout and distribution.
     pub groups: Vec<SubBattery>,
 }
 
@@ -61,8 +72,10 @@ impl Default for Battery { // {{{2
             len: 0.0,
             year: 1920,
             shells: 0,
+            loadout: Vec::new(),
             shell_wgt: None,
             kind: GunType::default(),
+            max_elevation: Self::ELEVATION,
 
             mount_num: 0,
             mount_kind: MountType::default(),
@@ -70,10 +83,7 @@ impl Default for Battery { // {{{2
             armor_back: 0.0,
             armor_barb: 0.0,
 
-            groups: vec![
-                SubBattery::default(),
-                SubBattery::default(),
-            ],
+            groups: vec![SubBattery::default()],
         }
     }
 }
@@ -83,6 +93,77 @@ impl Battery { // {{{2
     ///
     const CORDITE_FACTOR: f64 = 0.2444444;
 
+    /// Default maximum elevation of a mount in degrees.
+    ///
+    pub const ELEVATION: f64 = 30.0;
+
+    /// Elevation in degrees needed to engage aircraft overhead.
+    ///
+    const AA_ELEVATION: f64 = 80.0;
+
+    /// Number of groups in each battery of a SpringSharp file.
+    ///
+    pub const SS_GROUPS: usize = 2;
+
+    // default_elevation {{{3
+    /// Maximum elevation of batteries in files saved before it was added.
+    ///
+    fn default_elevation() -> f64 {
+        Self::ELEVATION
+    }
+
+    // add_group {{{3
+    /// Add an empty group of mounts after the existing ones and return it.
+    ///
+    pub fn add_group(&mut self) -> &mut SubBattery {
+        self.groups.push(SubBattery::default());
+        self.groups.last_mut().unwrap()
+    }
+
+    // remove_group {{{3
+    /// Remove a group of mounts, moving the following groups up.
+    ///
+    pub fn remove_group(&mut self, i: usize) -> Option<SubBattery> {
+        (i < self.groups.len()).then(|| self.groups.remove(i))
+    }
+
+    // group_mounts {{{3
+    /// Number of mounts placed in the battery's groups. This should match
+    /// mount_num.
+    ///
+    pub fn group_mounts(&self) -> u32 {
+        self.groups.iter().map(|g| g.num_mounts()).sum()
+    }
+
+    // mounts_below {{{3
+    /// Number of mounts below the waterline in all groups.
+    ///
+    pub fn mounts_below(&self) -> u32 {
+        self.groups.iter().map(|g| g.below).sum()
+    }
+
+    // kg {{{3
+    /// Height above the keel of the centre of gravity of the guns, mounts
+    /// and gun armour given the height of the deck.
+    ///
+    /// Each level of superfiring raises a mount and each deck below lowers
+    /// it by the same step that super_() uses.
+    ///
+    pub fn kg(&self, deck: f64) -> f64 {
+        let step = (self.diam * 0.6).max(7.5);
+        let base = deck + self.house_hgt() / 2.0;
+
+        let (mounts, levels) = self.groups.iter().fold((0, 0), |(mounts, levels), g| (
+            mounts + g.num_mounts(),
+            levels +
+                (g.above * if g.two_mounts_up { 2 } else { 1 }) as i32 -
+                (g.below * if g.lower_deck    { 2 } else { 1 }) as i32,
+        ));
+        if mounts == 0 { return base; }
+
+        base + step * levels as f64 / mounts as f64
+    }
+
     // broad_and_below {{{3
     /// Returns true if the battery has Broadside mounts
     /// and any guns are mounted below the waterline.
@@ -327,7 +408,110 @@ impl Battery { // {{{2
     /// Weight of the battery magazine.
     ///
     pub fn mag_wgt(&self) -> f64 {
-        (self.num * self.shells) as f64 * self.shell_wgt() / Ship::POUND2TON * (1.0 + Self::CORDITE_FACTOR)
+        let ratio: f64 = self.mag_mix().iter()
+            .map(|(shell, share)| share * shell.wgt_ratio())
+            .sum();
+
+        (self.num * self.shells) as f64 * self.shell_wgt() * ratio / Ship::POUND2TON * (1.0 + Self::CORDITE_FACTOR)
+    }
+
+    // mag_mix {{{3
+    /// Fraction of the magazine given to each type of shell, ignoring
+    /// negative shares.
+    ///
+    pub fn mag_mix(&self) -> Vec<(ShellType, f64)> {
+        let total: f64 = self.loadout.iter().map(|(_, share)| share.max(0.0)).sum();
+
+        if total <= 0.0 { return vec![(ShellType::AP, 1.0)]; }
+
+        self.loadout.iter()
+            .filter(|(_, share)| *share > 0.0)
+            .map(|(shell, share)| (*shell, share / total))
+            .collect()
+    }
+
+    // mag_desc {{{3
+    /// Describe the mix of shells in the magazine.
+    ///
+    pub fn mag_desc(&self) -> String {
+        self.mag_mix().iter()
+            .map(|(shell, share)| format!("{:.0}% {}", share * 100.0, shell))
+            .collect::<Vec<String>>()
+            .join(", ")
+    }
+
+    // rof {{{3
+    /// Estimated rounds per minute from each gun.
+    ///
+    /// Rate of fire falls with calibre, is set by the gun's breech
+    /// mechanism and by how the mount is worked, and improves until 1900
+    /// as loading gear matures.
+    ///
+    pub fn rof(&self) -> f64 {
+        if self.diam <= 0.0 { return 0.0; } // Catch divide by zero
+
+        let year = (1.0 - (1900.0 - self.year as f64).max(0.0) * 0.02).max(0.3);
+
+        40.0 / self.diam.max(0.3) *
+            self.kind.rof_factor() *
+            self.mount_kind.rof_factor() *
+            year
+    }
+
+    // shells_min {{{3
+    /// Shells fired per minute by the battery.
+    ///
+    pub fn shells_min(&self) -> f64 {
+        self.num as f64 * self.rof()
+    }
+
+    // wgt_min {{{3
+    /// Weight of shells fired per minute by the battery (lbs).
+    ///
+    pub fn wgt_min(&self) -> f64 {
+        self.shells_min() * self.shell_wgt()
+    }
+
+    // aa_band {{{3
+    /// Range band the battery defends against aircraft in, if any.
+    ///
+    pub fn aa_band(&self) -> Option<AABand> {
+        if self.num == 0 || self.kind.aa_factor() == 0.0 { return None; }
+
+        Some(AABand::from(self.diam))
+    }
+
+    // aa_score {{{3
+    /// Anti-aircraft effectiveness of the battery.
+    ///
+    /// Weight of fire per minute adjusted for the type of gun and mount,
+    /// how high the guns can elevate and the fire control of the year.
+    ///
+    pub fn aa_score(&self) -> f64 {
+        if self.aa_band().is_none() { return 0.0; }
+
+        let year = ((self.year as f64 - 1900.0) / 40.0).clamp(0.1, 1.5);
+        let elevation = (self.max_elevation / Self::AA_ELEVATION).clamp(0.0, 1.0);
+
+        self.wgt_min() / 100.0 *
+            self.kind.aa_factor() *
+            self.mount_kind.aa_factor() *
+            elevation *
+            year
+    }
+
+    // range_table {{{3
+    /// Range table of the battery's gun up to its maximum elevation.
+    ///
+    pub fn range_table(&self) -> Vec<Shot> {
+        Gun::from(self).range_table()
+    }
+
+    // max_range {{{3
+    /// Maximum range of the battery's gun in yards.
+    ///
+    pub fn max_range(&self) -> f64 {
+        Gun::from(self).max_range()
     }
 }
 
@@ -365,6 +549,11 @@ impl Battery {
         eprintln!("mount_wgt() = {}", self.mount_wgt());
         eprintln!("broadside_wgt() = {}", self.broadside_wgt());
         eprintln!("mag_wgt() = {}", self.mag_wgt());
+        eprintln!("rof() = {}", self.rof());
+        eprintln!("shells_min() = {}", self.shells_min());
+        eprintln!("wgt_min() = {}", self.wgt_min());
+        eprintln!("max_range() = {}", self.max_range());
+        eprintln!("aa_score() = {}", self.aa_score());
         eprintln!("");
 
         for (i, g) in self.groups.iter().enumerate() {
@@ -381,6 +570,85 @@ mod battery {
     use super::*;
     use crate::test_support::*;
 
+    // Test group_mounts {{{3
+    macro_rules! test_group_mounts {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, below, groups) = $value;
+
+                    let mut btry = Battery::default();
+                    btry.groups.clear();
+                    for (above, on, below) in groups {
+                        let g = btry.add_group();
+                        g.above = above;
+                        g.on = on;
+                        g.below = below;
+                    }
+
+                    assert_eq!(expected, btry.group_mounts());
+                    assert_eq!(below, btry.mounts_below());
+                }
+            )*
+        }
+    }
+    test_group_mounts! {
+        // name:                (mounts, below, [(above, on, below)])
+        group_mounts_none:      (0, 0, []),
+        group_mounts_one:       (4, 0, [(2, 2, 0)]),
+        group_mounts_three:     (9, 2, [(1, 1, 0), (0, 1, 0), (0, 4, 2)]),
+    }
+
+    // Test remove_group {{{3
+    #[test]
+    fn remove_group() {
+        let mut btry = Battery::default();
+        btry.add_group().on = 2;
+        btry.add_group().on = 3;
+
+        assert_eq!(2, btry.remove_group(1).unwrap().on);
+        assert_eq!(vec![0, 3], btry.groups.iter().map(|g| g.on).collect::<Vec<_>>());
+        assert!(btry.remove_group(2).is_none());
+    }
+
+    // Test kg {{{3
+    macro_rules! test_kg {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, diam, groups) = $value;
+
+                    let mut btry = Battery { diam, ..Default::default() };
+                    btry.groups.clear();
+                    for (above, on, below, two_mounts_up, lower_deck) in groups {
+                        let g = btry.add_group();
+                        g.above = above;
+                        g.on = on;
+                        g.below = below;
+                        g.two_mounts_up = two_mounts_up;
+                        g.lower_deck = lower_deck;
+                    }
+
+                    // Height above the centre of a gunhouse on the deck
+                    let base = 20.0 + btry.house_hgt() / 2.0;
+                    assert_eq!(expected, to_place(btry.kg(20.0) - base, 3));
+                }
+            )*
+        }
+    }
+    test_kg! {
+        // name:         (above base, diam, [(above, on, below, two_mounts_up, lower_deck)])
+        kg_no_mounts:    (0.0, 12.0, vec![]),
+        kg_on_deck:      (0.0, 12.0, vec![(0, 4, 0, false, false)]),
+        kg_superfiring:  (3.75, 12.0, vec![(1, 1, 0, false, false)]),
+        kg_two_up:       (7.5, 12.0, vec![(1, 0, 0, true, false), (0, 1, 0, false, false)]),
+        kg_large_gun:    (4.8, 16.0, vec![(1, 1, 0, false, false)]),
+        kg_casemates:    (-5.0, 6.0, vec![(0, 2, 4, false, false)]),
+        kg_lower_deck:   (-10.0, 6.0, vec![(0, 2, 4, false, true)]),
+    }
+
     // Test broad_and_below {{{3
     macro_rules! test_broad_and_below {
         ($($name:ident: $value:expr,)*) => {
@@ -448,6 +716,7 @@ mod battery {
                     btry.num = group_1_mounts + group_2_mounts;
                     btry.mount_num = group_1_mounts + group_2_mounts;
 
+                    btry.add_group();
                     btry.groups[0].above = group_1_mounts;
                     btry.groups[1].on = group_2_mounts;
 
@@ -489,6 +758,7 @@ mod battery {
 
                     btry.mount_num = group_1_mounts + group_2_mounts;
 
+                    btry.add_group();
                     btry.groups[0].on = group_1_mounts;
                     btry.groups[1].on = group_2_mounts;
 
@@ -536,7 +806,6 @@ mod battery {
                     btry.diam = 10.0;
 
                     btry.groups[0].on = 2;
-                    btry.groups[1].on = 0;
 
                     btry.groups[0].layout = GunLayoutType::Single;
 
@@ -590,7 +859,6 @@ mod battery {
                     btry.diam = 10.0;
 
                     btry.groups[0].on = 2;
-                    btry.groups[1].on = 0;
 
                     btry.groups[0].layout = GunLayoutType::Single;
 
@@ -624,7 +892,6 @@ mod battery {
                     // Assume they are all single mounts
                     btry.mount_num = btry.num;
                     btry.groups[0].on = btry.num;
-                    btry.groups[1].on = 0;
 
                     btry.groups[0].layout = GunLayoutType::Single;
 
@@ -661,6 +928,7 @@ mod battery {
                     let mut btry = Battery::default();
                     btry.mount_num = g0_mounts + g1_mounts;
 
+                    btry.add_group();
                     btry.groups[0].on = g0_mounts;
                     btry.groups[1].on = g1_mounts;
                     btry.groups[0].layout = GunLayoutType::Twin;
@@ -765,12 +1033,9 @@ mod battery {
                     btry.kind = GunType::AntiAir;
 
                     btry.groups[0].on = 1;
-                    btry.groups[1].on = 0;
                     btry.groups[0].layout = GunLayoutType::Single;
-                    btry.groups[1].layout = GunLayoutType::Single;
 
-                    btry.mount_num = btry.groups[0].num_mounts() +
-                        btry.groups[1].num_mounts();
+                    btry.mount_num = btry.group_mounts();
 
                     println!("{}", btry.mount_wgt());
                     assert!(expected == to_place(btry.mount_wgt(), 2));
@@ -832,6 +1097,148 @@ mod battery {
         mag_wgt_test_1: (5.56, 10, 10, 100.0),
         mag_wgt_test_2: (1.0+Battery::CORDITE_FACTOR, 1, 1, Ship::POUND2TON),
     }
+
+    // Test mag_mix {{{3
+    macro_rules! test_mag_mix {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (mag_wgt, desc, loadout) = $value;
+
+                    let mut btry = Battery::default();
+                    btry.num = 10;
+                    btry.shells = 10;
+                    btry.set_shell_wgt(100.0);
+                    btry.loadout = loadout;
+
+                    assert_eq!(mag_wgt, to_place(btry.mag_wgt(), 2));
+                    assert_eq!(desc, btry.mag_desc());
+                }
+            )*
+        }
+    }
+    test_mag_mix! {
+        // name:            (mag_wgt, desc, loadout)
+        mag_mix_default:    (5.56, "100% AP", vec![]),
+        mag_mix_ap:         (5.56, "100% AP", vec![(ShellType::AP, 50.0)]),
+        mag_mix_half:       (4.86, "50% AP, 50% HE", vec![(ShellType::AP, 50.0), (ShellType::HE, 50.0)]),
+        mag_mix_negative:   (4.17, "100% HE", vec![(ShellType::AP, -50.0), (ShellType::HE, 50.0)]),
+        mag_mix_zero:       (5.56, "100% AP", vec![(ShellType::HE, 0.0)]),
+    }
+
+    // Test rof {{{3
+    macro_rules! test_rof {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, diam, kind, mount_kind, year) = $value;
+
+                    let mut btry = Battery::default();
+                    btry.diam = diam;
+                    btry.kind = kind;
+                    btry.mount_kind = mount_kind;
+                    btry.year = year;
+
+                    assert_eq!(expected, to_place(btry.rof(), 2));
+                }
+            )*
+        }
+    }
+    test_rof! {
+        // name:         (rof, diam, kind, mount_kind, year)
+        rof_no_gun:      (0.0, 0.0, GunType::BreechLoading, MountType::ClosedBarbette, 1920),
+        rof_turret:      (2.5, 16.0, GunType::BreechLoading, MountType::ClosedBarbette, 1920),
+        rof_muzzle:      (0.24, 12.0, GunType::MuzzleLoading, MountType::ColesTurret, 1870),
+        rof_qf:          (11.0, 6.0, GunType::QuickFiring, MountType::Deck, 1900),
+        rof_qf_early:    (8.8, 6.0, GunType::QuickFiring, MountType::Deck, 1890),
+        rof_mg:          (880.0, 0.5, GunType::MachineGun, MountType::Deck, 1920),
+    }
+
+    // Test max_range {{{3
+    macro_rules! test_max_range {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, diam, elevation) = $value;
+
+                    let mut btry = Battery::default();
+                    btry.diam = diam;
+                    btry.len = 45.0;
+                    btry.max_elevation = elevation;
+
+                    assert_eq!(expected, btry.max_range().round());
+                    assert_eq!(expected, btry.range_table().last().map_or(0.0, |s| s.range).round());
+                }
+            )*
+        }
+    }
+    test_max_range! {
+        // name:               (max_range, diam, max_elevation)
+        max_range_no_gun:      (0.0, 0.0, 30.0),
+        max_range_flat:        (0.0, 8.0, 0.0),
+        max_range_low:         (19_499.0, 8.0, 15.0),
+        max_range_high:        (28_606.0, 8.0, 30.0),
+    }
+
+    // Test aa_score {{{3
+    macro_rules! test_aa_score {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (band, score, kind, diam, elevation) = $value;
+
+                    let mut btry = Battery::default();
+                    btry.num = 8;
+                    btry.diam = diam;
+                    btry.kind = kind;
+                    btry.mount_kind = MountType::ClosedBarbette;
+                    btry.year = 1940;
+                    btry.max_elevation = elevation;
+                    btry.set_shell_wgt(diam.powf(3.0) / 2.0);
+
+                    assert_eq!(band, btry.aa_band());
+                    assert_eq!(score, to_place(btry.aa_score(), 2));
+                }
+            )*
+        }
+    }
+    test_aa_score! {
+        // name:          (band, score, kind, diam, max_elevation)
+        aa_score_main:    (None, 0.0, GunType::BreechLoading, 14.0, 30.0),
+        aa_score_dp:      (Some(AABand::Long), 46.8, GunType::DualPurpose, 5.0, 85.0),
+        aa_score_dp_low:  (Some(AABand::Long), 23.4, GunType::DualPurpose, 5.0, 40.0),
+        aa_score_pompom:  (Some(AABand::Medium), 10.24, GunType::RapidFire, 1.6, 80.0),
+        aa_score_mg:      (Some(AABand::Short), 4.0, GunType::MachineGun, 0.5, 80.0),
+    }
+
+    // Test shells_min {{{3
+    macro_rules! test_shells_min {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (shells, wgt, num, shell_wgt) = $value;
+
+                    let mut btry = Battery::default();
+                    btry.diam = 8.0;
+                    btry.mount_kind = MountType::ClosedBarbette;
+                    btry.num = num;
+                    btry.set_shell_wgt(shell_wgt);
+
+                    assert_eq!((shells, wgt), (to_place(btry.shells_min(), 2), to_place(btry.wgt_min(), 2)));
+                }
+            )*
+        }
+    }
+    test_shells_min! {
+        // name:            (shells_min, wgt_min, num, shell_wgt)
+        shells_min_none:    (0.0, 0.0, 0, 250.0),
+        shells_min_eight:   (40.0, 10_000.0, 8, 250.0),
+    }
 }
 
 // GunType {{{1
@@ -946,6 +1353,35 @@ impl GunType { // {{{2
             Self::MachineGun    => 1.0,
         }
     }
+
+    // rof_factor {{{3
+    /// Multiplier for rate of fire.
+    ///
+    pub fn rof_factor(&self) -> f64 {
+        match self {
+            Self::MuzzleLoading => 0.3,
+            Self::BreechLoading => 1.0,
+            Self::QuickFiring   => 1.5,
+            Self::AntiAir       => 1.5,
+            Self::DualPurpose   => 1.3,
+            Self::RapidFire     => 2.5,
+            Self::MachineGun    => 10.0,
+        }
+    }
+
+    // aa_factor {{{3
+    /// Multiplier for anti-aircraft effectiveness. Guns not designed to
+    /// engage aircraft are not counted.
+    ///
+    pub fn aa_factor(&self) -> f64 {
+        match self {
+            Self::AntiAir     => 1.0,
+            Self::DualPurpose => 0.9,
+            Self::RapidFire   => 1.0,
+            Self::MachineGun  => 1.0,
+            _                 => 0.0,
+        }
+    }
 }
 
 // Testing GunType {{{2
@@ -1205,6 +1641,37 @@ impl MountType { // {{{2
             Self::Casemate       =>0.5,
         }
     }
+
+    // rof_factor {{{3
+    /// Multiplier for rate of fire.
+    ///
+    pub fn rof_factor(&self) -> f64 {
+        match self {
+            Self::Broadside      => 0.8,
+            Self::ColesTurret    => 0.6,
+            Self::OpenBarbette   => 0.9,
+            Self::ClosedBarbette => 1.0,
+            Self::DeckAndHoist   => 1.0,
+            Self::Deck           => 1.1,
+            Self::Casemate       => 0.9,
+        }
+    }
+
+    // aa_factor {{{3
+    /// Multiplier for anti-aircraft effectiveness from how quickly the
+    /// mount can train and how much sky it can cover.
+    ///
+    pub fn aa_factor(&self) -> f64 {
+        match self {
+            Self::Broadside      => 0.3,
+            Self::ColesTurret    => 0.3,
+            Self::OpenBarbette   => 0.8,
+            Self::ClosedBarbette => 1.0,
+            Self::DeckAndHoist   => 1.0,
+            Self::Deck           => 0.9,
+            Self::Casemate       => 0.5,
+        }
+    }
 }
 
 // Testing MountType {{{2
@@ -1300,6 +1767,181 @@ mod mount_type {
 
 }
 
+// ShellType {{{1
+/// Types of shell carried in a magazine.
+///
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+pub enum ShellType {
+    /// Armor piercing.
+    #[default]
+    AP,
+    /// Common or semi-armor piercing.
+    Common,
+    /// High explosive.
+    HE,
+    /// Shrapnel or time fuzed anti-aircraft.
+    Shrapnel,
+}
+
+impl FromStr for ShellType { // {{{2
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "ap"                 => Ok(Self::AP),
+            "common" | "sap"     => Ok(Self::Common),
+            "he"                 => Ok(Self::HE),
+            "shrapnel" | "aa"    => Ok(Self::Shrapnel),
+            _ => Err(format!("Unknown shell type '{}': use one of ap, common, he, shrapnel", s)),
+        }
+    }
+}
+
+impl fmt::Display for ShellType { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::AP       => "AP",
+                Self::Common   => "common",
+                Self::HE       => "HE",
+                Self::Shrapnel => "shrapnel",
+            }
+        )
+    }
+}
+
+impl ShellType { // {{{2
+    // wgt_ratio {{{3
+    /// Weight of the shell relative to an armor piercing shell.
+    ///
+    pub fn wgt_ratio(&self) -> f64 {
+        match self {
+            Self::AP       => 1.0,
+            Self::Common   => 0.9,
+            Self::HE       => 0.75,
+            Self::Shrapnel => 0.8,
+        }
+    }
+
+    // pen_factor {{{3
+    /// Armor penetration relative to an armor piercing shell of the same
+    /// weight.
+    ///
+    pub fn pen_factor(&self) -> f64 {
+        match self {
+            Self::AP       => 1.0,
+            Self::Common   => 0.75,
+            Self::HE       => 0.3,
+            Self::Shrapnel => 0.2,
+        }
+    }
+
+    // damage_factor {{{3
+    /// Damage to the hull relative to an armor piercing shell of the same
+    /// weight.
+    ///
+    pub fn damage_factor(&self) -> f64 {
+        match self {
+            Self::AP       => 1.0,
+            Self::Common   => 1.1,
+            Self::HE       => 1.25,
+            Self::Shrapnel => 0.5,
+        }
+    }
+}
+
+// Testing ShellType {{{2
+#[cfg(test)]
+mod shell_type {
+    use super::*;
+
+    // Test from_str {{{3
+    macro_rules! test_from_str {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, s) = $value;
+
+                    assert_eq!(expected, s.parse::<ShellType>().ok());
+                }
+            )*
+        }
+    }
+    test_from_str! {
+        // name:            (shell, string)
+        from_str_ap:        (Some(ShellType::AP), "AP"),
+        from_str_sap:       (Some(ShellType::Common), "sap"),
+        from_str_he:        (Some(ShellType::HE), "he"),
+        from_str_aa:        (Some(ShellType::Shrapnel), "aa"),
+        from_str_unknown:   (None, "canister"),
+    }
+}
+
+// AABand {{{1
+/// Range bands of anti-aircraft fire.
+///
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AABand {
+    /// Heavy guns engaging high level bombers.
+    Long,
+    /// Automatic cannon engaging dive bombers.
+    Medium,
+    /// Machine guns engaging close in attackers.
+    Short,
+}
+
+impl From<f64> for AABand { // {{{2
+    fn from(diam: f64) -> Self {
+        if diam >= 3.0 {
+            Self::Long
+        } else if diam >= 1.0 {
+            Self::Medium
+        } else {
+            Self::Short
+        }
+    }
+}
+
+impl fmt::Display for AABand { // {{{2
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}",
+            match self {
+                Self::Long   => "Long range",
+                Self::Medium => "Medium range",
+                Self::Short  => "Short range",
+            }
+        )
+    }
+}
+
+// Testing AABand {{{2
+#[cfg(test)]
+mod aa_band {
+    use super::*;
+
+    // Test from {{{3
+    macro_rules! test_from {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, diam) = $value;
+
+                    assert_eq!(expected, AABand::from(diam));
+                }
+            )*
+        }
+    }
+    test_from! {
+        // name:          (band, diam)
+        from_heavy:       (AABand::Long, 5.0),
+        from_three_inch:  (AABand::Long, 3.0),
+        from_bofors:      (AABand::Medium, 1.57),
+        from_oerlikon:    (AABand::Short, 0.79),
+    }
+}
+
 // SubBattery {{{1
 /// Gun grouping within a battery.
 ///
@@ -1717,7 +2359,7 @@ impl GunDistributionType { // {{{2
     // mounts_fwd {{{3
     /// Number of mounts that are placed forward.
     ///
-    fn mounts_fwd(&self, tot: u32, fwd_len: f64) -> u32 {
+    pub(crate) fn mounts_fwd(&self, tot: u32, fwd_len: f64) -> u32 {
         // Divide n by 2 and round
         fn half(n: u32) -> u32 {
             f64::round(n as f64 / 2.0) as u32
@@ -2220,6 +2862,14 @@ pub struct Torpedoes {
 }
 
 impl Torpedoes { // {{{2
+    /// Speed of each setting as a fraction of the highest speed.
+    ///
+    const SETTINGS: [f64; 3] = [1.0, 0.75, 0.6];
+
+    /// Range factor (yds·kts² per in²·ft) for 1910 torpedoes.
+    ///
+    const RANGE_FACTOR: f64 = 600.0;
+
     // wgt {{{3
     /// Weight of all torpedoes and mounts in the set.
     ///
@@ -2231,12 +2881,77 @@ impl Torpedoes { // {{{2
     /// Weight of torpedoes in the set.
     ///
     pub fn wgt_weaps(&self) -> f64 {
+        self.wgt_each() * self.num as f64
+    }
+
+    // wgt_each {{{3
+    /// Weight of each torpedo.
+    ///
+    pub fn wgt_each(&self) -> f64 {
+        PI * self.diam.powf(2.0) * self.len /
         (
-            PI * self.diam.powf(2.0) * self.len /
-            (
-                (f64::max(1907.0 - self.year as f64, 0.0) + 25.0) * 937.0
-            ) + (self.year as f64 - 1890.0) * 0.004
-        ) * self.num as f64
+            (f64::max(1907.0 - self.year as f64, 0.0) + 25.0) * 937.0
+        ) + (self.year as f64 - 1890.0) * 0.004
+    }
+
+    // warhead {{{3
+    /// Estimated warhead weight of each torpedo (lbs).
+    ///
+    /// The share of the torpedo given to the warhead grows from 8% in 1880
+    /// to 20% by 1940 as engines get lighter for their power.
+    ///
+    pub fn warhead(&self) -> f64 {
+        if self.diam <= 0.0 || self.len <= 0.0 { return 0.0; }
+
+        let share = (0.08 + (self.year as f64 - 1880.0) * 0.002).clamp(0.08, 0.2);
+
+        self.wgt_each() * Ship::POUND2TON * share
+    }
+
+    // vmax {{{3
+    /// Estimated speed of the fastest setting (kts).
+    ///
+    pub fn vmax(&self) -> f64 {
+        if self.diam <= 0.0 || self.len <= 0.0 { return 0.0; }
+
+        (18.0 + (self.year as f64 - 1880.0) * 0.5).clamp(18.0, 50.0) * (self.diam / 18.0).powf(0.25)
+    }
+
+    // range {{{3
+    /// Estimated range (yds) when set to run at a given speed (kts).
+    ///
+    /// The energy carried grows with the volume of the torpedo and with
+    /// better engines and fuels over the years, and is used up in
+    /// proportion to the square of the speed.
+    ///
+    pub fn range(&self, v: f64) -> f64 {
+        if v <= 0.0 { return 0.0; }
+
+        let energy = ((self.year as f64 - 1880.0) / 30.0).max(0.3);
+
+        Self::RANGE_FACTOR * energy * self.diam.powf(2.0) * self.len / v.powf(2.0)
+    }
+
+    // settings {{{3
+    /// Estimated speed (kts) and range (yds) settings, fastest first.
+    ///
+    pub fn settings(&self) -> Vec<(f64, f64)> {
+        if self.vmax() <= 0.0 { return Vec::new(); }
+
+        Self::SETTINGS.iter()
+            .map(|f| self.vmax() * f)
+            .map(|v| (v, self.range(v)))
+            .collect()
+    }
+
+    // settings_desc {{{3
+    /// Describe the range and speed settings.
+    ///
+    pub fn settings_desc(&self) -> String {
+        self.settings().iter()
+            .map(|(v, range)| format!("{} yds at {:.0} kts", format_num!(",.0", range.round()), v))
+            .collect::<Vec<String>>()
+            .join(", ")
     }
 
     // wgt_mounts {{{3
@@ -2261,6 +2976,79 @@ impl Torpedoes { // {{{2
     }
 }
 
+// Testing Torpedoes {{{2
+#[cfg(test)]
+mod torpedoes {
+    use super::*;
+    use crate::test_support::*;
+
+    fn get_torp(diam: f64, len: f64, year: u32) -> Torpedoes {
+        Torpedoes {
+            year,
+            num: 4,
+            diam,
+            len,
+            ..Default::default()
+        }
+    }
+
+    // Test warhead {{{3
+    macro_rules! test_warhead {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, diam, len, year) = $value;
+
+                    assert_eq!(expected, get_torp(diam, len, year).warhead().round());
+                }
+            )*
+        }
+    }
+
+    test_warhead! {
+        // name:            (lbs, diam, len, year)
+        warhead_none:       (0.0, 0.0, 0.0, 1920),
+        warhead_1890:       (53.0, 14.0, 15.0, 1890),
+        warhead_1910:       (257.0, 18.0, 17.0, 1910),
+        warhead_1940:       (699.0, 21.0, 23.0, 1940),
+        warhead_1950:       (717.0, 21.0, 23.0, 1950),
+    }
+
+    // Test settings {{{3
+    macro_rules! test_settings {
+        ($($name:ident: $value:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (expected, diam, len, year): (Vec<(f64, f64)>, _, _, _) = $value;
+
+                    let settings: Vec<(f64, f64)> = get_torp(diam, len, year).settings().iter()
+                        .map(|(v, range)| (to_place(*v, 1), range.round()))
+                        .collect();
+
+                    assert_eq!(expected, settings);
+                }
+            )*
+        }
+    }
+
+    test_settings! {
+        // name:            ([(kts, yds)], diam, len, year)
+        settings_none:      (vec![], 0.0, 0.0, 1920),
+        settings_1910:      (vec![(33.0, 3035.0), (24.8, 5395.0), (19.8, 8430.0)], 18.0, 17.0, 1910),
+        settings_1940:      (vec![(49.9, 4891.0), (37.4, 8695.0), (29.9, 13586.0)], 21.0, 23.0, 1940),
+    }
+
+    // Test wgt_each {{{3
+    #[test]
+    fn wgt_each() {
+        let torp = get_torp(21.0, 23.0, 1940);
+
+        assert_eq!(to_place(torp.wgt_weaps(), 6), to_place(torp.wgt_each() * 4.0, 6));
+    }
+}
+
 // TorpedoMountType {{{1
 /// Type of torpedo mount.
 ///